@@ -0,0 +1,71 @@
+// Compares the spatial index against going over every node, for the kind of radius
+// queries the game does every frame (bullet collision, attachment, targeting).
+// Run with `cargo bench --bench spatial_index`.
+
+// Only part of the index is used here
+#[allow(dead_code)]
+#[path = "../src/spatial/resources.rs"]
+mod resources;
+
+use bevy::prelude::*;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use resources::SpatialIndex;
+use std::time::Instant;
+
+const CELL_SIZE: f32 = 32.;
+const QUERY_RADIUS: f32 = 16.;
+const QUERIES: usize = 1000;
+// Nodes are spread over a square that grows with the node count, so the density stays similar
+const AREA_PER_NODE: f32 = 400.;
+
+fn main() {
+    for node_count in [100, 1000, 5000, 10000] {
+        let mut rng = StdRng::seed_from_u64(0);
+        let half_extent = (node_count as f32 * AREA_PER_NODE).sqrt() / 2.;
+        let mut random_position = || {
+            Vec2::new(
+                rng.gen_range(-half_extent..half_extent),
+                rng.gen_range(-half_extent..half_extent),
+            )
+        };
+
+        let nodes: Vec<(Entity, Vec2)> = (0..node_count)
+            .map(|i| (Entity::from_raw(i), random_position()))
+            .collect();
+        let centers: Vec<Vec2> = (0..QUERIES).map(|_| random_position()).collect();
+
+        // Going over every node for every query
+        let start = Instant::now();
+        let mut brute_force_hits = 0;
+        for center in &centers {
+            brute_force_hits += nodes
+                .iter()
+                .filter(|(_, position)| position.distance(*center) < QUERY_RADIUS)
+                .count();
+        }
+        let brute_force_time = start.elapsed();
+
+        // Rebuilding the index, like every frame, and then querying it
+        let start = Instant::now();
+        let mut spatial_index = SpatialIndex::new(CELL_SIZE);
+        for (entity, position) in &nodes {
+            spatial_index.insert(*entity, *position, 0.);
+        }
+        let mut index_hits = 0;
+        for center in &centers {
+            index_hits += spatial_index.query(*center, QUERY_RADIUS).count();
+        }
+        let index_time = start.elapsed();
+
+        assert_eq!(brute_force_hits, index_hits);
+
+        println!(
+            "{:>6} nodes, {} queries: brute force {:>10.3?}, spatial index {:>10.3?} ({:.1}x)",
+            node_count,
+            QUERIES,
+            brute_force_time,
+            index_time,
+            brute_force_time.as_secs_f64() / index_time.as_secs_f64()
+        );
+    }
+}
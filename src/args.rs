@@ -0,0 +1,36 @@
+use std::env;
+
+/// The command line arguments of the game.
+#[derive(Debug, Default)]
+pub struct Args {
+    /// Run the gameplay without a window, renderer or audio.
+    pub headless: bool,
+    /// The amount of frames to simulate before exiting in headless mode.
+    pub frames: Option<u32>,
+    /// The seed every run starts with, a new one is picked for every run if `None`.
+    pub seed: Option<u64>,
+    /// A replay file to play back in the first run.
+    pub replay: Option<String>,
+    /// The asset folder to read from in headless mode, instead of the one Bevy would use.
+    pub assets: Option<String>,
+}
+
+impl Args {
+    pub fn from_env() -> Self {
+        let mut args = Self::default();
+        let mut iter = env::args().skip(1);
+
+        while let Some(arg) = iter.next() {
+            match arg.as_str() {
+                "--headless" => args.headless = true,
+                "--frames" => args.frames = iter.next().and_then(|value| value.parse().ok()),
+                "--seed" => args.seed = iter.next().and_then(|value| value.parse().ok()),
+                "--replay" => args.replay = iter.next(),
+                "--assets" => args.assets = iter.next(),
+                _ => {}
+            }
+        }
+
+        args
+    }
+}
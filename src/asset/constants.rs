@@ -2,12 +2,9 @@
 pub const ASSET_FONTS_DEFAULT: &str = "fonts/minimal5x7.ttf";
 
 // Sprites
-pub const ASSET_SPRITES_ZAPPER: &str = "sprites/zapper.png";
 pub const ASSET_SPRITES_DEBRIS: &str = "sprites/debris.png";
 pub const ASSET_SPRITES_PLAYER: &str = "sprites/player.png";
-pub const ASSET_SPRITES_SHIELD: &str = "sprites/shield.png";
-pub const ASSET_SPRITES_FORCEFIELD: &str = "sprites/forcefield.png";
-pub const ASSET_SPRITES_CANNON: &str = "sprites/cannon.png";
+pub const ASSET_SPRITES_ARROW: &str = "sprites/arrow.png";
 
 // Audio
 pub const ASSET_AUDIO_DEATH: &str = "sounds/deathsound.wav";
@@ -5,5 +5,6 @@ mod systems;
 
 pub use self::{
     plugin::{AssetPlugin, AssetSystem},
-    resources::{AudioHandles, FontHandles, SpriteHandles},
+    resources::{AudioHandles, FontHandles, LoadingAssets, SpriteHandles},
+    systems::load_folder,
 };
@@ -9,11 +9,8 @@ pub struct FontHandles {
 #[derive(Default)]
 pub struct SpriteHandles {
     pub debris: Handle<Image>,
-    pub zapper: Handle<Image>,
     pub player: Handle<Image>,
-    pub shield: Handle<Image>,
-    pub forcefield: Handle<Image>,
-    pub cannon: Handle<Image>,
+    pub arrow: Handle<Image>,
 }
 
 #[derive(Default)]
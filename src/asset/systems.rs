@@ -1,15 +1,14 @@
 use super::{
     constants::{
         ASSET_AUDIO_DEATH, ASSET_AUDIO_EXPLOSION, ASSET_AUDIO_HIT, ASSET_AUDIO_INTRO,
-        ASSET_AUDIO_LASER, ASSET_AUDIO_MUSIC, ASSET_FONTS_DEFAULT, ASSET_SPRITES_CANNON,
-        ASSET_SPRITES_DEBRIS, ASSET_SPRITES_FORCEFIELD, ASSET_SPRITES_PLAYER, ASSET_SPRITES_SHIELD,
-        ASSET_SPRITES_ZAPPER,
+        ASSET_AUDIO_LASER, ASSET_AUDIO_MUSIC, ASSET_FONTS_DEFAULT, ASSET_SPRITES_ARROW,
+        ASSET_SPRITES_DEBRIS, ASSET_SPRITES_PLAYER,
     },
     resources::LoadingAssets,
     AudioHandles, FontHandles, SpriteHandles,
 };
 use crate::schedule::GameState;
-use bevy::asset::LoadState;
+use bevy::asset::{AssetServerError, LoadState};
 use bevy::prelude::*;
 
 pub fn load_assets_system(
@@ -24,11 +23,8 @@ pub fn load_assets_system(
 
     // Sprites
     sprite_handles.debris = asset_server.load(ASSET_SPRITES_DEBRIS);
-    sprite_handles.zapper = asset_server.load(ASSET_SPRITES_ZAPPER);
     sprite_handles.player = asset_server.load(ASSET_SPRITES_PLAYER);
-    sprite_handles.shield = asset_server.load(ASSET_SPRITES_SHIELD);
-    sprite_handles.forcefield = asset_server.load(ASSET_SPRITES_FORCEFIELD);
-    sprite_handles.cannon = asset_server.load(ASSET_SPRITES_CANNON);
+    sprite_handles.arrow = asset_server.load(ASSET_SPRITES_ARROW);
 
     // Audio
     audio_handles.death = asset_server.load(ASSET_AUDIO_DEATH);
@@ -45,11 +41,8 @@ pub fn load_assets_system(
         font_handles.default.clone_untyped(),
         // Sprites
         sprite_handles.debris.clone_untyped(),
-        sprite_handles.zapper.clone_untyped(),
         sprite_handles.player.clone_untyped(),
-        sprite_handles.shield.clone_untyped(),
-        sprite_handles.forcefield.clone_untyped(),
-        sprite_handles.cannon.clone_untyped(),
+        sprite_handles.arrow.clone_untyped(),
         // Audio
         audio_handles.death.clone_untyped(),
         audio_handles.hit.clone_untyped(),
@@ -75,3 +68,20 @@ pub fn check_if_assets_are_loaded_system(
         _ => {}
     }
 }
+
+/// Loads every asset in the `folder`. Folders can't be listed on the web, so there the `files`
+/// named for it are loaded one by one instead.
+pub fn load_folder(
+    asset_server: &AssetServer,
+    folder: &str,
+    files: &[&str],
+) -> Result<Vec<HandleUntyped>, AssetServerError> {
+    if cfg!(target_arch = "wasm32") {
+        Ok(files
+            .iter()
+            .map(|file| asset_server.load_untyped(format!("{}/{}", folder, file).as_str()))
+            .collect())
+    } else {
+        asset_server.load_folder(folder)
+    }
+}
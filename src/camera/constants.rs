@@ -0,0 +1,15 @@
+// Offset in units and rotation in radians at full trauma
+pub const MAX_SHAKE_OFFSET: f32 = 3.;
+pub const MAX_SHAKE_ROTATION: f32 = 0.03;
+// How fast the camera wobbles, in radians per second
+pub const SHAKE_FREQUENCY: f32 = 40.;
+// Trauma lost per second
+pub const TRAUMA_DECAY: f32 = 1.5;
+
+pub const TRAUMA_PER_DAMAGE: f32 = 0.02;
+// Trauma of an enemy dying right next to the camera, it fades out with the distance
+pub const ENEMY_DEATH_TRAUMA: f32 = 0.4;
+pub const ENEMY_DEATH_SHAKE_RANGE: f32 = 150.;
+
+// Touchpads scroll in pixels, this many make up one wheel notch
+pub const PIXELS_PER_ZOOM_STEP: f32 = 100.;
@@ -1,9 +1,13 @@
 mod components;
+mod constants;
 mod plugin;
 mod resources;
 mod systems;
 
 pub use self::{
-    components::*, plugin::CameraPlugin, plugin::CameraSystem, resources::RandomNumberResource,
+    components::*,
+    plugin::CameraPlugin,
+    plugin::CameraSystem,
+    resources::{CameraSettings, RandomNumberResource},
     systems::camera_follow_system,
 };
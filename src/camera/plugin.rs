@@ -1,11 +1,18 @@
 use super::{
     camera_follow_system,
-    resources::RandomNumberResource,
-    systems::{camera_zoom_system, spawn_camera_system},
+    resources::{CameraSettings, CameraShake, CameraZoom},
+    systems::{
+        apply_camera_shake_system, camera_zoom_system, insert_random_number_resource_system,
+        read_camera_zoom_input_system, remove_camera_shake_system, shake_on_enemy_killed_system,
+        shake_on_player_death_system, shake_on_player_hit_system, spawn_camera_system,
+        update_cursor_world_position_system,
+    },
+};
+use crate::{
+    player::{PlayerInputSystem, PlayerSystem},
+    schedule::{in_game_or_after, GameState},
 };
-use crate::{player::PlayerSystem, schedule::GameState};
 use bevy::prelude::*;
-use rand::Rng;
 
 #[derive(Debug, PartialEq, Eq, Clone, Hash, SystemLabel)]
 pub struct CameraSystem;
@@ -14,30 +21,49 @@ pub struct CameraPlugin;
 
 impl Plugin for CameraPlugin {
     fn build(&self, app: &mut App) {
-        app.add_system_set(
-            SystemSet::on_enter(GameState::BeforeSplashScreen)
-                .label(CameraSystem)
-                .with_system(spawn_camera_system),
-        )
-        .add_system_set(
-            SystemSet::on_update(GameState::InGame)
-                .label(CameraSystem)
-                .after(PlayerSystem)
-                .with_system(camera_follow_system)
-                .with_system(camera_zoom_system),
-        )
-        .insert_resource(RandomNumberResource {
-            rand1: {
-                let r1 = rand::thread_rng().gen_range(500.0f32..1000.0f32);
-                let r2 = (rand::thread_rng().gen_range(0.0f32..1.0f32).round() * 2.0) - 1.0;
-                r1 * r2
-            },
-
-            rand2: {
-                let r1 = rand::thread_rng().gen_range(500.0f32..1000.0f32);
-                let r2 = (rand::thread_rng().gen_range(0.0f32..1.0f32).round() * 2.0) - 1.0;
-                r1 * r2
-            },
-        });
+        app.insert_resource(CameraSettings::default())
+            .init_resource::<CameraShake>()
+            .init_resource::<CameraZoom>()
+            .add_system_set(
+                SystemSet::on_enter(GameState::BeforeSplashScreen)
+                    .label(CameraSystem)
+                    .with_system(spawn_camera_system),
+            )
+            .add_system_set(
+                SystemSet::on_update(GameState::InGame)
+                    .label(CameraSystem)
+                    .before(PlayerInputSystem)
+                    .with_system(update_cursor_world_position_system),
+            )
+            .add_system_set(
+                SystemSet::on_update(GameState::InGame)
+                    .label(CameraSystem)
+                    .after(PlayerSystem)
+                    .with_system(camera_follow_system)
+                    .with_system(read_camera_zoom_input_system.before(camera_zoom_system))
+                    .with_system(camera_zoom_system)
+                    .with_system(shake_on_player_hit_system)
+                    .with_system(shake_on_enemy_killed_system),
+            )
+            .add_system_set(
+                SystemSet::on_enter(GameState::AfterInGame)
+                    .label(CameraSystem)
+                    .with_system(shake_on_player_death_system),
+            )
+            // The shake goes on while the player explodes
+            .add_system_set(
+                SystemSet::new()
+                    .with_run_criteria(in_game_or_after)
+                    .label(CameraSystem)
+                    .with_system(remove_camera_shake_system.before(camera_follow_system))
+                    .with_system(
+                        apply_camera_shake_system
+                            .after(remove_camera_shake_system)
+                            .after(camera_follow_system)
+                            .after(shake_on_player_hit_system)
+                            .after(shake_on_enemy_killed_system),
+                    ),
+            )
+            .add_startup_system(insert_random_number_resource_system);
     }
 }
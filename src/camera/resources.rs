@@ -1,4 +1,77 @@
+use bevy::prelude::*;
+
 pub struct RandomNumberResource {
     pub rand1: f32,
     pub rand2: f32,
 }
+
+#[derive(Debug)]
+pub struct CameraSettings {
+    shake: bool,
+    // Bounds of the zoom the player picks, on top of the zoom that follows the ship size
+    pub min_zoom: f32,
+    pub max_zoom: f32,
+    // Factor one wheel notch or key press zooms by
+    pub zoom_step: f32,
+    // How much further out the tactical view is
+    pub tactical_zoom: f32,
+    // Share of the way to the cursor the camera leads the ship by, and the most it does in units
+    pub look_ahead: f32,
+    pub max_look_ahead: f32,
+}
+
+impl Default for CameraSettings {
+    fn default() -> Self {
+        Self {
+            shake: true,
+            min_zoom: 0.5,
+            max_zoom: 2.5,
+            zoom_step: 1.15,
+            tactical_zoom: 3.,
+            look_ahead: 0.25,
+            max_look_ahead: 60.,
+        }
+    }
+}
+
+impl CameraSettings {
+    pub fn toggle_shake(&mut self) {
+        self.shake = !self.shake;
+    }
+
+    pub fn shake(&self) -> bool {
+        self.shake
+    }
+}
+
+/// The zoom the player picked, 1 is the zoom that just follows the ship size and
+/// bigger values show more.
+pub struct CameraZoom {
+    pub factor: f32,
+    pub tactical: bool,
+}
+
+impl Default for CameraZoom {
+    fn default() -> Self {
+        Self {
+            factor: 1.,
+            tactical: false,
+        }
+    }
+}
+
+// How shaken up the camera is, the shake is layered on top of where it would be otherwise
+#[derive(Default)]
+pub struct CameraShake {
+    // Between 0 and 1, the shake grows with its square
+    pub trauma: f32,
+    // What was added to the camera transform this frame
+    pub offset: Vec2,
+    pub rotation: f32,
+}
+
+impl CameraShake {
+    pub fn add_trauma(&mut self, trauma: f32) {
+        self.trauma = (self.trauma + trauma).min(1.);
+    }
+}
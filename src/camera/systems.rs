@@ -1,11 +1,39 @@
 use crate::{
     camera::MainCamera,
-    player::PlayerRoot,
+    enemy::EnemyKilled,
+    events::DamageApplied,
+    player::{CursorWorldPosition, Player, PlayerRoot},
+    random::GameRng,
     starfield::{CustomMaterial, Starfield},
 };
-use bevy::prelude::*;
+use bevy::{
+    input::mouse::{MouseScrollUnit, MouseWheel},
+    prelude::*,
+    render::camera::RenderTarget,
+};
+use rand::Rng;
+
+use super::{
+    constants::{
+        ENEMY_DEATH_SHAKE_RANGE, ENEMY_DEATH_TRAUMA, MAX_SHAKE_OFFSET, MAX_SHAKE_ROTATION,
+        PIXELS_PER_ZOOM_STEP, SHAKE_FREQUENCY, TRAUMA_DECAY, TRAUMA_PER_DAMAGE,
+    },
+    resources::{CameraSettings, CameraShake, CameraZoom, RandomNumberResource},
+};
 
-use super::resources::RandomNumberResource;
+// A random start position of the camera and starfield, on either side of the origin
+pub fn insert_random_number_resource_system(mut commands: Commands, mut rng: ResMut<GameRng>) {
+    let mut random_offset = || {
+        let r1 = rng.gen_range(500.0f32..1000.0f32);
+        let r2 = (rng.gen_range(0.0f32..1.0f32).round() * 2.0) - 1.0;
+        r1 * r2
+    };
+
+    commands.insert_resource(RandomNumberResource {
+        rand1: random_offset(),
+        rand2: random_offset(),
+    });
+}
 
 pub fn spawn_camera_system(mut commands: Commands, random: Res<RandomNumberResource>) {
     commands
@@ -31,13 +59,20 @@ pub fn camera_follow_system(
         (With<Starfield>, Without<MainCamera>, Without<PlayerRoot>),
     >,
     mut materials: ResMut<Assets<CustomMaterial>>,
+    camera_settings: Res<CameraSettings>,
+    cursor_world_position: Res<CursorWorldPosition>,
 ) {
     let player_transform = player_query.single();
     let mut camera_transform = camera_query.single_mut();
 
-    let lerped = camera_transform
-        .translation
-        .lerp(player_transform.translation, 0.1);
+    // Lead the ship a bit towards where the player aims
+    let look_ahead = cursor_world_position.0.map_or(Vec2::ZERO, |cursor| {
+        ((cursor - player_transform.translation).truncate() * camera_settings.look_ahead)
+            .clamp_length_max(camera_settings.max_look_ahead)
+    });
+    let target = player_transform.translation + look_ahead.extend(0.);
+
+    let lerped = camera_transform.translation.lerp(target, 0.1);
 
     camera_transform.translation.x = lerped.x;
     camera_transform.translation.y = lerped.y;
@@ -56,14 +91,53 @@ pub fn camera_follow_system(
     }
 }
 
+// Zoom in and out with the mouse wheel or plus and minus, tab switches to the tactical view
+pub fn read_camera_zoom_input_system(
+    camera_settings: Res<CameraSettings>,
+    mut camera_zoom: ResMut<CameraZoom>,
+    mut mouse_wheel_events: EventReader<MouseWheel>,
+    keyboard_input: Res<Input<KeyCode>>,
+) {
+    // Positive steps zoom in
+    let mut steps: f32 = mouse_wheel_events
+        .iter()
+        .map(|event| match event.unit {
+            MouseScrollUnit::Line => event.y,
+            MouseScrollUnit::Pixel => event.y / PIXELS_PER_ZOOM_STEP,
+        })
+        .sum();
+    if keyboard_input.any_just_pressed([KeyCode::Equals, KeyCode::NumpadAdd]) {
+        steps += 1.;
+    }
+    if keyboard_input.any_just_pressed([KeyCode::Minus, KeyCode::NumpadSubtract]) {
+        steps -= 1.;
+    }
+
+    camera_zoom.factor = (camera_zoom.factor * camera_settings.zoom_step.powf(-steps))
+        .clamp(camera_settings.min_zoom, camera_settings.max_zoom);
+
+    if keyboard_input.just_pressed(KeyCode::Tab) {
+        camera_zoom.tactical = !camera_zoom.tactical;
+    }
+}
+
 pub fn camera_zoom_system(
     mut camera_query: Query<&mut Transform, (With<MainCamera>, Without<PlayerRoot>)>,
     mut player_root_query: Query<&PlayerRoot, With<PlayerRoot>>,
+    camera_settings: Res<CameraSettings>,
+    camera_zoom: Res<CameraZoom>,
 ) {
     let mut camera_transform = camera_query.single_mut();
     let root_component = player_root_query.get_single_mut().unwrap();
 
-    let new_scale = 0.25 + (0.01 * (root_component.dist / 8.0));
+    // Bigger ships need more room, the player's zoom comes on top
+    let tactical_zoom = if camera_zoom.tactical {
+        camera_settings.tactical_zoom
+    } else {
+        1.
+    };
+    let new_scale =
+        (0.25 + (0.01 * (root_component.dist / 8.0))) * camera_zoom.factor * tactical_zoom;
 
     let lerped = camera_transform
         .scale
@@ -72,3 +146,98 @@ pub fn camera_zoom_system(
     camera_transform.scale.x = lerped.x;
     camera_transform.scale.y = lerped.y;
 }
+
+// Convert the cursor position on the screen into a position in the world, which the player aims at
+pub fn update_cursor_world_position_system(
+    windows: Res<Windows>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    mut cursor_world_position: ResMut<CursorWorldPosition>,
+) {
+    let (camera, camera_transform) = camera_query.single();
+
+    let window = if let RenderTarget::Window(id) = camera.target {
+        windows.get(id).unwrap()
+    } else {
+        windows.get_primary().unwrap()
+    };
+
+    cursor_world_position.0 = window.cursor_position().map(|cursor_position| {
+        let window_size = Vec2::new(window.width() as f32, window.height() as f32);
+        let ndc = (cursor_position / window_size) * 2.0 - Vec2::ONE;
+        let ndc_to_world = camera_transform.compute_matrix() * camera.projection_matrix().inverse();
+        ndc_to_world.project_point3(ndc.extend(-1.0))
+    });
+}
+
+// The harder the hit on the player, the more the camera shakes
+pub fn shake_on_player_hit_system(
+    camera_settings: Res<CameraSettings>,
+    mut camera_shake: ResMut<CameraShake>,
+    mut damage_applied_events: EventReader<DamageApplied>,
+    player_query: Query<(), With<Player>>,
+) {
+    for hit in damage_applied_events.iter() {
+        if camera_settings.shake() && player_query.contains(hit.target) {
+            camera_shake.add_trauma(hit.damage as f32 * TRAUMA_PER_DAMAGE);
+        }
+    }
+}
+
+pub fn shake_on_enemy_killed_system(
+    camera_settings: Res<CameraSettings>,
+    mut camera_shake: ResMut<CameraShake>,
+    mut enemy_killed_events: EventReader<EnemyKilled>,
+    camera_query: Query<&Transform, With<MainCamera>>,
+) {
+    let camera_position = camera_query.single().translation.truncate();
+    for position in enemy_killed_events
+        .iter()
+        .filter_map(|event| event.position)
+    {
+        let closeness = 1. - camera_position.distance(position) / ENEMY_DEATH_SHAKE_RANGE;
+        if camera_settings.shake() && closeness > 0. {
+            camera_shake.add_trauma(ENEMY_DEATH_TRAUMA * closeness);
+        }
+    }
+}
+
+pub fn shake_on_player_death_system(
+    camera_settings: Res<CameraSettings>,
+    mut camera_shake: ResMut<CameraShake>,
+) {
+    if camera_settings.shake() {
+        camera_shake.add_trauma(1.);
+    }
+}
+
+// Takes last frame's shake off again, so following the player isn't thrown off by it
+pub fn remove_camera_shake_system(
+    camera_shake: Res<CameraShake>,
+    mut camera_query: Query<&mut Transform, With<MainCamera>>,
+) {
+    let mut camera_transform = camera_query.single_mut();
+    camera_transform.translation -= camera_shake.offset.extend(0.);
+    camera_transform.rotate_z(-camera_shake.rotation);
+}
+
+pub fn apply_camera_shake_system(
+    time: Res<Time>,
+    camera_settings: Res<CameraSettings>,
+    mut camera_shake: ResMut<CameraShake>,
+    mut camera_query: Query<&mut Transform, With<MainCamera>>,
+) {
+    if !camera_settings.shake() {
+        camera_shake.trauma = 0.;
+    }
+    camera_shake.trauma = (camera_shake.trauma - TRAUMA_DECAY * time.delta_seconds()).max(0.);
+
+    // Smooth wobbles on every axis, out of step with each other
+    let t = time.seconds_since_startup() as f32 * SHAKE_FREQUENCY;
+    let shake = camera_shake.trauma * camera_shake.trauma;
+    camera_shake.offset = Vec2::new(t.sin(), (t * 1.3 + 1.).sin()) * MAX_SHAKE_OFFSET * shake;
+    camera_shake.rotation = (t * 0.7 + 2.).sin() * MAX_SHAKE_ROTATION * shake;
+
+    let mut camera_transform = camera_query.single_mut();
+    camera_transform.translation += camera_shake.offset.extend(0.);
+    camera_transform.rotate_z(camera_shake.rotation);
+}
@@ -0,0 +1,314 @@
+use crate::components::Collider;
+use bevy::prelude::*;
+
+// Half the size of the 8x8 node sprites
+pub const NODE_HALF_SIZE: f32 = 4.;
+
+impl Default for Collider {
+    // Covers the whole sprite of a node and turns with it
+    fn default() -> Self {
+        Collider::Obb {
+            half_width: NODE_HALF_SIZE,
+            half_height: NODE_HALF_SIZE,
+        }
+    }
+}
+
+impl Collider {
+    /// Radius of the smallest circle around the entity that contains the whole collider.
+    pub fn bounding_radius(&self, scale: Vec3) -> f32 {
+        let scale = scale.truncate().abs().max_element();
+        match *self {
+            Collider::Circle { radius } => radius * scale,
+            Collider::Aabb {
+                half_width,
+                half_height,
+            }
+            | Collider::Obb {
+                half_width,
+                half_height,
+            } => Vec2::new(half_width, half_height).length() * scale,
+        }
+    }
+
+    /// The same shape, grown by `margin` on every side.
+    pub fn inflated(&self, margin: f32) -> Self {
+        match *self {
+            Collider::Circle { radius } => Collider::Circle {
+                radius: radius + margin,
+            },
+            Collider::Aabb {
+                half_width,
+                half_height,
+            } => Collider::Aabb {
+                half_width: half_width + margin,
+                half_height: half_height + margin,
+            },
+            Collider::Obb {
+                half_width,
+                half_height,
+            } => Collider::Obb {
+                half_width: half_width + margin,
+                half_height: half_height + margin,
+            },
+        }
+    }
+
+    fn in_world(&self, transform: &Transform) -> WorldShape {
+        let center = transform.translation.truncate();
+        let scale = transform.scale.truncate().abs();
+        match *self {
+            Collider::Circle { radius } => WorldShape::Circle {
+                center,
+                radius: radius * scale.max_element(),
+            },
+            Collider::Aabb {
+                half_width,
+                half_height,
+            } => WorldShape::Box(WorldBox {
+                center,
+                half_extents: Vec2::new(half_width, half_height) * scale,
+                axes: [Vec2::X, Vec2::Y],
+            }),
+            Collider::Obb {
+                half_width,
+                half_height,
+            } => {
+                let x_axis = (transform.rotation * Vec3::X)
+                    .truncate()
+                    .normalize_or_zero();
+                WorldShape::Box(WorldBox {
+                    center,
+                    half_extents: Vec2::new(half_width, half_height) * scale,
+                    axes: [x_axis, x_axis.perp()],
+                })
+            }
+        }
+    }
+}
+
+// A collider with the transform of its entity applied
+enum WorldShape {
+    Circle { center: Vec2, radius: f32 },
+    Box(WorldBox),
+}
+
+struct WorldBox {
+    center: Vec2,
+    half_extents: Vec2,
+    // The directions of the local x and y axis
+    axes: [Vec2; 2],
+}
+
+impl WorldBox {
+    // Half the length of the box projected onto the axis
+    fn projected_extent(&self, axis: Vec2) -> f32 {
+        self.half_extents.x * self.axes[0].dot(axis).abs()
+            + self.half_extents.y * self.axes[1].dot(axis).abs()
+    }
+
+    fn overlaps_circle(&self, center: Vec2, radius: f32) -> bool {
+        // The closest point of the box to the circle, in the space of the box
+        let offset = center - self.center;
+        let local = Vec2::new(offset.dot(self.axes[0]), offset.dot(self.axes[1]));
+        let closest = local.clamp(-self.half_extents, self.half_extents);
+
+        local.distance(closest) < radius
+    }
+
+    // Where the segment enters the box, along the segment from 0 to 1
+    fn segment_entry(&self, from: Vec2, to: Vec2) -> Option<f32> {
+        let local = |point: Vec2| {
+            let offset = point - self.center;
+            Vec2::new(offset.dot(self.axes[0]), offset.dot(self.axes[1]))
+        };
+        let start = local(from);
+        let direction = local(to) - start;
+        if start.abs().cmplt(self.half_extents).all() {
+            return None;
+        }
+
+        // Clip the segment against the two slabs the box is made of
+        let mut enter = 0_f32;
+        let mut exit = 1_f32;
+        for axis in 0..2 {
+            let extent = self.half_extents[axis];
+            if direction[axis].abs() < f32::EPSILON {
+                if start[axis].abs() > extent {
+                    return None;
+                }
+                continue;
+            }
+            let a = (-extent - start[axis]) / direction[axis];
+            let b = (extent - start[axis]) / direction[axis];
+            enter = enter.max(a.min(b));
+            exit = exit.min(a.max(b));
+        }
+
+        if enter <= exit {
+            Some(enter)
+        } else {
+            None
+        }
+    }
+
+    fn overlaps_box(&self, other: &WorldBox) -> bool {
+        // Separating axis test, two boxes only need to be checked on their own axes
+        let offset = other.center - self.center;
+        self.axes.iter().chain(other.axes.iter()).all(|axis| {
+            offset.dot(*axis).abs() < self.projected_extent(*axis) + other.projected_extent(*axis)
+        })
+    }
+}
+
+/// The point where the segment from `from` to `to` enters the collider, given the world
+/// transform of its entity. Segments that start inside the collider don't enter it.
+pub fn segment_entry(
+    collider: &Collider,
+    transform: &Transform,
+    from: Vec2,
+    to: Vec2,
+) -> Option<Vec2> {
+    let direction = to - from;
+    let t = match collider.in_world(transform) {
+        WorldShape::Circle { center, radius } => {
+            let offset = from - center;
+            if offset.length_squared() < radius * radius {
+                return None;
+            }
+
+            // The smaller root of |offset + direction * t| = radius
+            let a = direction.length_squared();
+            let b = 2. * offset.dot(direction);
+            let c = offset.length_squared() - radius * radius;
+            let discriminant = b * b - 4. * a * c;
+            if a < f32::EPSILON || discriminant < 0. {
+                return None;
+            }
+            let t = (-b - discriminant.sqrt()) / (2. * a);
+            if !(0. ..=1.).contains(&t) {
+                return None;
+            }
+            t
+        }
+        WorldShape::Box(world_box) => world_box.segment_entry(from, to)?,
+    };
+
+    Some(from + direction * t)
+}
+
+/// Whether two colliders overlap, given the world transforms of their entities.
+/// Bullet hits and attachment go through this.
+pub fn colliders_overlap(
+    a: &Collider,
+    a_transform: &Transform,
+    b: &Collider,
+    b_transform: &Transform,
+) -> bool {
+    match (a.in_world(a_transform), b.in_world(b_transform)) {
+        (
+            WorldShape::Circle {
+                center: a_center,
+                radius: a_radius,
+            },
+            WorldShape::Circle {
+                center: b_center,
+                radius: b_radius,
+            },
+        ) => a_center.distance(b_center) < a_radius + b_radius,
+        (WorldShape::Circle { center, radius }, WorldShape::Box(world_box))
+        | (WorldShape::Box(world_box), WorldShape::Circle { center, radius }) => {
+            world_box.overlaps_circle(center, radius)
+        }
+        (WorldShape::Box(a_box), WorldShape::Box(b_box)) => a_box.overlaps_box(&b_box),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::FRAC_PI_4;
+
+    const BOX: Collider = Collider::Obb {
+        half_width: 4.,
+        half_height: 4.,
+    };
+
+    fn at(x: f32, y: f32) -> Transform {
+        Transform::from_xyz(x, y, 0.)
+    }
+
+    fn assert_near(a: Vec2, b: Vec2) {
+        assert!(a.distance(b) < 1e-4, "{} is not {}", a, b);
+    }
+
+    #[test]
+    fn circles_overlap_within_their_radii() {
+        let small = Collider::Circle { radius: 1. };
+        let large = Collider::Circle { radius: 4. };
+
+        assert!(colliders_overlap(&small, &at(4.5, 0.), &large, &at(0., 0.)));
+        assert!(!colliders_overlap(
+            &small,
+            &at(5.5, 0.),
+            &large,
+            &at(0., 0.)
+        ));
+        // The scale of the entity grows the radius
+        let scaled = at(7., 0.).with_scale(Vec3::splat(4.));
+        assert!(colliders_overlap(&small, &scaled, &large, &at(0., 0.)));
+    }
+
+    #[test]
+    fn boxes_overlap_depending_on_their_rotation() {
+        let rotated = at(9., 0.).with_rotation(Quat::from_rotation_z(FRAC_PI_4));
+
+        assert!(!colliders_overlap(&BOX, &at(0., 0.), &BOX, &at(9., 0.)));
+        // Turned, its corner reaches into the other box
+        assert!(colliders_overlap(&BOX, &at(0., 0.), &BOX, &rotated));
+        // An axis aligned box stays aligned no matter the rotation
+        let aabb = Collider::Aabb {
+            half_width: 4.,
+            half_height: 4.,
+        };
+        assert!(!colliders_overlap(&BOX, &at(0., 0.), &aabb, &rotated));
+    }
+
+    #[test]
+    fn circles_overlap_boxes_up_to_their_closest_point() {
+        let circle = Collider::Circle { radius: 2. };
+
+        assert!(colliders_overlap(&BOX, &at(0., 0.), &circle, &at(5., 0.)));
+        assert!(colliders_overlap(&circle, &at(5., 0.), &BOX, &at(0., 0.)));
+        // Close to the sides, but not to the corner
+        assert!(!colliders_overlap(&BOX, &at(0., 0.), &circle, &at(6., 6.)));
+    }
+
+    #[test]
+    fn segments_enter_circles_on_their_edge() {
+        let circle = Collider::Circle { radius: 2. };
+        let origin = at(0., 0.);
+
+        let entry = segment_entry(&circle, &origin, Vec2::new(-5., 0.), Vec2::new(5., 0.));
+        assert_near(entry.unwrap(), Vec2::new(-2., 0.));
+        // Stops short, passes by or starts inside
+        assert!(segment_entry(&circle, &origin, Vec2::new(-5., 0.), Vec2::new(-3., 0.)).is_none());
+        assert!(segment_entry(&circle, &origin, Vec2::new(-5., 3.), Vec2::new(5., 3.)).is_none());
+        assert!(segment_entry(&circle, &origin, Vec2::new(1., 0.), Vec2::new(5., 0.)).is_none());
+    }
+
+    #[test]
+    fn segments_enter_boxes_on_their_side() {
+        let from = Vec2::new(-10., 1.);
+        let to = Vec2::new(10., 1.);
+
+        let entry = segment_entry(&BOX, &at(0., 0.), from, to);
+        assert_near(entry.unwrap(), Vec2::new(-4., 1.));
+        // Turned, the segment runs into it closer to the corner
+        let rotated = at(0., 0.).with_rotation(Quat::from_rotation_z(FRAC_PI_4));
+        let entry = segment_entry(&BOX, &rotated, from, to);
+        assert_near(entry.unwrap(), Vec2::new(1. - 4. * 2_f32.sqrt(), 1.));
+        assert!(segment_entry(&BOX, &at(0., 0.), Vec2::new(0., 0.), to).is_none());
+        assert!(segment_entry(&BOX, &at(0., 6.), from, to).is_none());
+    }
+}
@@ -3,8 +3,9 @@ use bevy::prelude::Color;
 pub const COLOR_ACCENT: Color = Color::rgb(249.0 / 255.0, 72.0 / 255.0, 64.0 / 255.0);
 pub const COLOR_ENEMY: Color = Color::rgba(249.0 / 255.0, 72.0 / 255.0, 64.0 / 255.0, 0.7);
 pub const COLOR_PLAYER: Color = Color::rgba(62.0 / 255.0, 105.0 / 255.0, 255.0 / 255.0, 0.7);
+pub const COLOR_DRONES: Color = Color::rgba(64.0 / 255.0, 230.0 / 255.0, 140.0 / 255.0, 0.7);
+pub const COLOR_SALVAGERS: Color = Color::rgba(250.0 / 255.0, 190.0 / 255.0, 60.0 / 255.0, 0.7);
 
-#[allow(dead_code)]
 pub const COLOR_BACKGROUND_DARK: Color = Color::rgb(20.0 / 255.0, 17.0 / 255.0, 18.0 / 255.0);
 pub const COLOR_BACKGROUND_MEDIUM: Color = Color::rgb(69.0 / 255.0, 61.0 / 255.0, 62.0 / 255.0);
 pub const COLOR_BACKGROUND_LIGHT: Color = Color::rgb(118.0 / 255.0, 116.0 / 255.0, 117.0 / 255.0);
@@ -1,9 +1,17 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Component)]
-pub struct Collider;
+// Shape that other colliders are tested against, in the local space of the entity
+#[derive(Component, Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum Collider {
+    Circle { radius: f32 },
+    // Axis aligned, ignores the rotation of the entity
+    Aabb { half_width: f32, half_height: f32 },
+    // Oriented, turns with the entity
+    Obb { half_width: f32, half_height: f32 },
+}
 
-#[derive(Component)]
+#[derive(Component, Clone, Default, Serialize, Deserialize)]
 pub struct Velocity {
     pub x: f32,
     pub y: f32,
@@ -12,29 +20,114 @@ pub struct Velocity {
 
 // Components can hold data too, though
 // size: Amount of connected blocks
-#[derive(Component)]
+// health: Health of all the blocks together
+#[derive(Component, Clone, Serialize, Deserialize)]
 pub struct Properties {
     pub size: u32,
     pub health: u32,
 }
 
+// Hit points of a single node, a node is destroyed when they run out
+#[derive(Component, Clone, Serialize, Deserialize)]
+pub struct Health {
+    pub current: u32,
+    pub max: u32,
+}
+
+// What a hit is made of, so modules can be better at taking some than others
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DamageType {
+    // Plain bullets
+    #[default]
+    Kinetic,
+    Energy,
+    Explosive,
+}
+
+// Share of the damage of each type that is soaked up, negative values make a weakness
+#[derive(Component, Clone, Default, Serialize, Deserialize)]
+pub struct Resistances {
+    #[serde(default)]
+    pub kinetic: f32,
+    #[serde(default)]
+    pub energy: f32,
+    #[serde(default)]
+    pub explosive: f32,
+}
+
+impl Resistances {
+    /// The damage that gets through, rounded to whole hit points.
+    pub fn mitigate(&self, damage: u32, damage_type: DamageType) -> u32 {
+        let resistance = match damage_type {
+            DamageType::Kinetic => self.kinetic,
+            DamageType::Energy => self.energy,
+            DamageType::Explosive => self.explosive,
+        };
+        (damage as f32 * (1. - resistance.min(1.))).round() as u32
+    }
+}
+
 // Powerup nodes
 #[derive(Component)]
 pub struct Shield {}
 
-#[derive(Component)]
+#[derive(Component, Clone, Serialize, Deserialize)]
 pub struct ShieldForcefield {
-    // Health of the shield until the cooldown starts
+    // Health of the fully charged forcefield, the cooldown starts when it is used up
     pub health: u32,
     // Cooldown in seconds
     pub cooldown: f32,
+    #[serde(default)]
     pub cooldown_timer: f32,
+    // Health regained per second, once the cooldown is over
+    pub recharge_rate: f32,
+    // Damage taken that hasn't been recharged yet
+    #[serde(default)]
+    pub damage: f32,
+    // Applied to the hits the forcefield takes in place of the ship
+    #[serde(default)]
+    pub resistances: Resistances,
 }
 
-#[derive(Component)]
+impl ShieldForcefield {
+    // A forcefield is up once it has recharged a bit after its cooldown
+    pub fn is_up(&self) -> bool {
+        self.cooldown_timer <= 0. && self.damage < self.health as f32
+    }
+
+    /// Share of its health the forcefield has left, between 0 and 1.
+    pub fn strength(&self) -> f32 {
+        (1. - self.damage / self.health.max(1) as f32).clamp(0., 1.)
+    }
+
+    /// Takes a hit, after resistances, and returns the damage it took. Breaks the forcefield
+    /// when its health runs out.
+    pub fn absorb(&mut self, damage: u32, damage_type: DamageType) -> u32 {
+        let damage = self.resistances.mitigate(damage, damage_type);
+        self.damage += damage as f32;
+        if !self.is_up() {
+            self.damage = self.health as f32;
+            self.cooldown_timer = self.cooldown;
+        }
+        damage
+    }
+
+    /// Runs down the cooldown, and once it is over, recharges for the rest of the time.
+    pub fn recharge(&mut self, seconds: f32) {
+        if self.cooldown_timer > 0. {
+            self.cooldown_timer -= seconds;
+        } else {
+            // Recharge gradually, rather than coming back at full health
+            self.damage = (self.damage - self.recharge_rate * seconds).max(0.);
+        }
+    }
+}
+
+#[derive(Component, Clone, Serialize, Deserialize)]
 pub struct Zapper {
     pub damage: u32,
     pub fire_rate: f32,
+    #[serde(default)]
     pub cooldown_timer: f32,
     pub range: f32,
 }
@@ -42,19 +135,112 @@ pub struct Zapper {
 #[derive(Component)]
 pub struct ZapEffect;
 
-#[derive(Component)]
+#[derive(Component, Clone, Serialize, Deserialize)]
 pub struct Bullet {
     pub damage: u32,
-    pub enemy: bool,
+    #[serde(default)]
+    pub damage_type: DamageType,
+    // The cannon that fired it, lost when saving
+    #[serde(skip)]
+    pub source: Option<Entity>,
 }
 
-#[derive(Component)]
+#[derive(Component, Clone, Serialize, Deserialize)]
 pub struct Cannon {
     pub damage: u32,
+    #[serde(default)]
+    pub damage_type: DamageType,
     pub fire_rate: f32,
+    #[serde(default)]
     pub cooldown_timer: f32,
     pub range: f32,
 }
 
 #[derive(Component)]
 pub struct Projectile {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resistances_scale_the_damage_of_their_type() {
+        let resistances = Resistances {
+            kinetic: 0.5,
+            energy: -0.5,
+            explosive: 0.,
+        };
+
+        assert_eq!(resistances.mitigate(10, DamageType::Kinetic), 5);
+        assert_eq!(resistances.mitigate(10, DamageType::Energy), 15);
+        assert_eq!(resistances.mitigate(10, DamageType::Explosive), 10);
+    }
+
+    #[test]
+    fn mitigated_damage_is_rounded_and_never_below_zero() {
+        let resistances = Resistances {
+            kinetic: 0.25,
+            energy: 2.,
+            explosive: 0.,
+        };
+
+        // 7.5 rounds up
+        assert_eq!(resistances.mitigate(10, DamageType::Kinetic), 8);
+        // Resistances above 1 soak up everything and no more
+        assert_eq!(resistances.mitigate(10, DamageType::Energy), 0);
+        assert_eq!(Resistances::default().mitigate(3, DamageType::Energy), 3);
+    }
+
+    fn forcefield() -> ShieldForcefield {
+        ShieldForcefield {
+            health: 10,
+            cooldown: 2.,
+            cooldown_timer: 0.,
+            recharge_rate: 5.,
+            damage: 0.,
+            resistances: Resistances {
+                energy: 0.5,
+                ..default()
+            },
+        }
+    }
+
+    #[test]
+    fn forcefields_take_hits_after_their_resistances() {
+        let mut forcefield = forcefield();
+
+        assert_eq!(forcefield.absorb(4, DamageType::Energy), 2);
+        assert_eq!(forcefield.absorb(4, DamageType::Kinetic), 4);
+        assert!(forcefield.is_up());
+        assert!((forcefield.strength() - 0.4).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn forcefields_break_when_their_health_runs_out() {
+        let mut forcefield = forcefield();
+
+        // Overkill still only counts as the damage taken
+        assert_eq!(forcefield.absorb(12, DamageType::Kinetic), 12);
+        assert!(!forcefield.is_up());
+        assert_eq!(forcefield.strength(), 0.);
+        assert_eq!(forcefield.cooldown_timer, 2.);
+    }
+
+    #[test]
+    fn forcefields_recharge_once_the_cooldown_is_over() {
+        let mut forcefield = forcefield();
+        forcefield.absorb(10, DamageType::Kinetic);
+
+        // No recharging during the cooldown
+        forcefield.recharge(1.5);
+        assert!(!forcefield.is_up());
+        forcefield.recharge(0.5);
+        assert_eq!(forcefield.damage, 10.);
+        // Back up as soon as it has some health again, and full after a while
+        forcefield.recharge(0.5);
+        assert!(forcefield.is_up());
+        assert_eq!(forcefield.damage, 7.5);
+        forcefield.recharge(10.);
+        assert_eq!(forcefield.damage, 0.);
+    }
+}
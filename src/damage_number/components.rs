@@ -0,0 +1,8 @@
+use bevy::prelude::*;
+
+// Damage numbers are never despawned while playing, faded ones are hidden and reused
+#[derive(Component)]
+pub struct DamageNumber {
+    pub age: f32,
+    pub color: Color,
+}
@@ -0,0 +1,22 @@
+use crate::colors::COLOR_FOREGROUND;
+use bevy::prelude::Color;
+
+// Beyond this, new hits get no number so big battles stay readable and fast
+pub const MAX_DAMAGE_NUMBERS: usize = 100;
+
+// Above the particles, below the off-screen indicators
+pub const DAMAGE_NUMBER_Z: f32 = 0.92;
+
+// Font size in units, the font is drawn pixel for pixel at a zoom of 1
+pub const DAMAGE_NUMBER_FONT_SIZE: f32 = 8.;
+
+// Seconds a number is shown, and how fast it drifts up in units per second
+pub const DAMAGE_NUMBER_LIFETIME: f32 = 0.8;
+pub const DAMAGE_NUMBER_SPEED: f32 = 20.;
+
+// The player's own hits are colored by their damage type, everyone else's by their faction
+pub const COLOR_DAMAGE_KINETIC: Color = COLOR_FOREGROUND;
+pub const COLOR_DAMAGE_ENERGY: Color = Color::rgb(170.0 / 255.0, 120.0 / 255.0, 255.0 / 255.0);
+pub const COLOR_DAMAGE_EXPLOSIVE: Color = Color::rgb(255.0 / 255.0, 150.0 / 255.0, 50.0 / 255.0);
+// What a forcefield took instead of its ship
+pub const COLOR_DAMAGE_ABSORBED: Color = Color::rgb(90.0 / 255.0, 220.0 / 255.0, 255.0 / 255.0);
@@ -0,0 +1,11 @@
+mod components;
+mod constants;
+mod plugin;
+mod resources;
+mod systems;
+
+pub use self::{
+    components::DamageNumber,
+    plugin::{DamageNumberPlugin, DamageNumberSystem},
+    resources::DamageNumberSettings,
+};
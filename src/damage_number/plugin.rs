@@ -0,0 +1,35 @@
+use super::{
+    resources::DamageNumberPool,
+    systems::{
+        clear_damage_numbers_system, spawn_damage_numbers_system, update_damage_numbers_system,
+    },
+    DamageNumberSettings,
+};
+use crate::schedule::{in_game_or_after, GameState};
+use bevy::prelude::*;
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash, SystemLabel)]
+pub struct DamageNumberSystem;
+
+// The damage of every hit, floating up from where it landed
+pub struct DamageNumberPlugin;
+
+impl Plugin for DamageNumberPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DamageNumberPool>()
+            .init_resource::<DamageNumberSettings>()
+            // The numbers finish fading while the death of the player plays out
+            .add_system_set(
+                SystemSet::new()
+                    .with_run_criteria(in_game_or_after)
+                    .label(DamageNumberSystem)
+                    .with_system(spawn_damage_numbers_system)
+                    .with_system(update_damage_numbers_system.after(spawn_damage_numbers_system)),
+            )
+            .add_system_set(
+                SystemSet::on_exit(GameState::AfterInGame)
+                    .label(DamageNumberSystem)
+                    .with_system(clear_damage_numbers_system),
+            );
+    }
+}
@@ -0,0 +1,29 @@
+use bevy::prelude::*;
+
+/// The damage number entities that can be reused, and how many are in use.
+#[derive(Default)]
+pub struct DamageNumberPool {
+    pub free: Vec<Entity>,
+    pub live: usize,
+}
+
+#[derive(Debug)]
+pub struct DamageNumberSettings {
+    enabled: bool,
+}
+
+impl Default for DamageNumberSettings {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+impl DamageNumberSettings {
+    pub fn toggle(&mut self) {
+        self.enabled = !self.enabled;
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+}
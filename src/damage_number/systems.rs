@@ -0,0 +1,153 @@
+use super::{
+    constants::{
+        COLOR_DAMAGE_ABSORBED, COLOR_DAMAGE_ENERGY, COLOR_DAMAGE_EXPLOSIVE, COLOR_DAMAGE_KINETIC,
+        DAMAGE_NUMBER_FONT_SIZE, DAMAGE_NUMBER_LIFETIME, DAMAGE_NUMBER_SPEED, DAMAGE_NUMBER_Z,
+        MAX_DAMAGE_NUMBERS,
+    },
+    resources::DamageNumberPool,
+    DamageNumber, DamageNumberSettings,
+};
+use crate::{
+    asset::FontHandles,
+    components::DamageType,
+    events::{DamageApplied, ForcefieldHit},
+    faction::Faction,
+};
+use bevy::prelude::*;
+
+fn damage_type_color(damage_type: DamageType) -> Color {
+    match damage_type {
+        DamageType::Kinetic => COLOR_DAMAGE_KINETIC,
+        DamageType::Energy => COLOR_DAMAGE_ENERGY,
+        DamageType::Explosive => COLOR_DAMAGE_EXPLOSIVE,
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn spawn_damage_numbers_system(
+    mut commands: Commands,
+    font_handles: Res<FontHandles>,
+    settings: Res<DamageNumberSettings>,
+    mut pool: ResMut<DamageNumberPool>,
+    mut damage_applied_events: EventReader<DamageApplied>,
+    mut forcefield_hit_events: EventReader<ForcefieldHit>,
+    faction_query: Query<&Faction>,
+    mut number_query: Query<(
+        &mut DamageNumber,
+        &mut Transform,
+        &mut Text,
+        &mut Visibility,
+    )>,
+) {
+    if !settings.enabled() {
+        return;
+    }
+
+    let mut numbers: Vec<(String, Color, Vec2)> = Vec::new();
+    for hit in damage_applied_events.iter() {
+        if hit.damage == 0 {
+            continue;
+        }
+
+        let color = match hit.source.and_then(|source| faction_query.get(source).ok()) {
+            Some(Faction::Player) | None => damage_type_color(hit.damage_type),
+            Some(faction) => faction.color(),
+        };
+        numbers.push((hit.damage.to_string(), color, hit.position));
+    }
+    // Absorbed damage is in brackets, as the ship behind the forcefield got away unharmed
+    for hit in forcefield_hit_events.iter() {
+        if hit.damage == 0 {
+            continue;
+        }
+        numbers.push((
+            format!("({})", hit.damage),
+            COLOR_DAMAGE_ABSORBED,
+            hit.position,
+        ));
+    }
+
+    for (value, color, position) in numbers {
+        if pool.live >= MAX_DAMAGE_NUMBERS {
+            return;
+        }
+        pool.live += 1;
+
+        let number = DamageNumber { age: 0., color };
+        let translation = position.extend(DAMAGE_NUMBER_Z);
+
+        // Reuse a faded number if there is one
+        if let Some(entity) = pool.free.pop() {
+            if let Ok((mut old_number, mut transform, mut text, mut visibility)) =
+                number_query.get_mut(entity)
+            {
+                *old_number = number;
+                transform.translation = translation;
+                text.sections[0].value = value;
+                text.sections[0].style.color = color;
+                visibility.is_visible = true;
+                continue;
+            }
+        }
+
+        commands
+            .spawn_bundle(Text2dBundle {
+                text: Text::from_section(
+                    value,
+                    TextStyle {
+                        font: font_handles.default.clone(),
+                        font_size: DAMAGE_NUMBER_FONT_SIZE,
+                        color,
+                    },
+                )
+                .with_alignment(TextAlignment::CENTER),
+                transform: Transform::from_translation(translation),
+                ..default()
+            })
+            .insert(number);
+    }
+}
+
+pub fn update_damage_numbers_system(
+    time: Res<Time>,
+    mut pool: ResMut<DamageNumberPool>,
+    mut number_query: Query<(
+        Entity,
+        &mut DamageNumber,
+        &mut Transform,
+        &mut Text,
+        &mut Visibility,
+    )>,
+) {
+    for (entity, mut number, mut transform, mut text, mut visibility) in number_query.iter_mut() {
+        if !visibility.is_visible {
+            continue;
+        }
+
+        number.age += time.delta_seconds();
+        if number.age >= DAMAGE_NUMBER_LIFETIME {
+            visibility.is_visible = false;
+            pool.free.push(entity);
+            pool.live -= 1;
+            continue;
+        }
+
+        // Drift up and fade out
+        transform.translation.y += DAMAGE_NUMBER_SPEED * time.delta_seconds();
+        let mut color = number.color;
+        color.set_a(number.color.a() * (1. - number.age / DAMAGE_NUMBER_LIFETIME));
+        text.sections[0].style.color = color;
+    }
+}
+
+// The pool is filled again in the next run
+pub fn clear_damage_numbers_system(
+    mut commands: Commands,
+    mut pool: ResMut<DamageNumberPool>,
+    number_query: Query<Entity, With<DamageNumber>>,
+) {
+    for entity in number_query.iter() {
+        commands.entity(entity).despawn();
+    }
+    *pool = DamageNumberPool::default();
+}
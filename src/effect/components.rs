@@ -0,0 +1,10 @@
+use bevy::prelude::*;
+
+// A straight line between two points that fades out over its lifetime
+#[derive(Component)]
+pub struct Beam {
+    pub color: Color,
+    // Seconds until the beam is gone
+    pub lifetime: f32,
+    pub age: f32,
+}
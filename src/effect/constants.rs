@@ -0,0 +1,2 @@
+// Thickness of a beam in units
+pub const BEAM_WIDTH: f32 = 2.;
@@ -0,0 +1,11 @@
+mod components;
+mod constants;
+mod plugin;
+mod spawners;
+mod systems;
+
+pub use self::{
+    components::Beam,
+    plugin::{EffectPlugin, EffectSystem},
+    spawners::spawn_beam,
+};
@@ -0,0 +1,29 @@
+use super::{systems::fade_beams_system, Beam};
+use crate::{
+    despawn_recursive::despawn_entities_recursive_system,
+    schedule::{in_game_or_after, GameState},
+};
+use bevy::prelude::*;
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash, SystemLabel)]
+pub struct EffectSystem;
+
+// Short lived visuals, like the beams of the zappers
+pub struct EffectPlugin;
+
+impl Plugin for EffectPlugin {
+    fn build(&self, app: &mut App) {
+        // Beams fired just before the player died still fade out during the explosion
+        app.add_system_set(
+            SystemSet::new()
+                .with_run_criteria(in_game_or_after)
+                .label(EffectSystem)
+                .with_system(fade_beams_system),
+        )
+        .add_system_set(
+            SystemSet::on_exit(GameState::AfterInGame)
+                .label(EffectSystem)
+                .with_system(despawn_entities_recursive_system::<Beam>),
+        );
+    }
+}
@@ -0,0 +1,28 @@
+use super::{constants::BEAM_WIDTH, Beam};
+use bevy::prelude::*;
+
+// One sprite, stretched from `from` to `to`
+pub fn spawn_beam(commands: &mut Commands, from: Vec2, to: Vec2, color: Color, lifetime: f32) {
+    let direction = to - from;
+
+    commands
+        .spawn()
+        .insert_bundle(SpriteBundle {
+            transform: Transform {
+                translation: from.lerp(to, 0.5).extend(0.),
+                rotation: Quat::from_rotation_z(direction.y.atan2(direction.x)),
+                ..default()
+            },
+            sprite: Sprite {
+                color,
+                custom_size: Some(Vec2::new(direction.length(), BEAM_WIDTH)),
+                ..default()
+            },
+            ..default()
+        })
+        .insert(Beam {
+            color,
+            lifetime,
+            age: 0.,
+        });
+}
@@ -0,0 +1,20 @@
+use super::Beam;
+use bevy::prelude::*;
+
+pub fn fade_beams_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut beam_query: Query<(Entity, &mut Beam, &mut Sprite)>,
+) {
+    for (entity, mut beam, mut sprite) in beam_query.iter_mut() {
+        beam.age += time.delta_seconds();
+        if beam.age >= beam.lifetime {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let mut color = beam.color;
+        color.set_a(beam.color.a() * (1. - beam.age / beam.lifetime));
+        sprite.color = color;
+    }
+}
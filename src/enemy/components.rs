@@ -5,12 +5,6 @@ pub struct Enemy;
 
 #[derive(Component)]
 pub struct EnemyRoot {
-    pub enemy_type: EnemyType,
-}
-
-#[derive(Component)]
-pub enum EnemyType {
-    Shieldy,
-    Zappy,
-    Boomy,
+    // Id of the blueprint the ship was built from
+    pub blueprint: String,
 }
@@ -1,2 +1,9 @@
-// Movement
-pub const ENEMY_SPEED: f32 = 75.;
+// Blueprints
+pub const ASSET_ENEMIES_FOLDER: &str = "enemies";
+pub const BLUEPRINT_EXTENSION: &str = "enemy.ron";
+// Every file in the enemies folder, for the web where the folder can't be listed
+pub const BLUEPRINT_FILES: &[&str] = &["boomy.enemy.ron", "shieldy.enemy.ron", "zappy.enemy.ron"];
+// Distance between the centers of two cells
+pub const BLUEPRINT_CELL_SIZE: f32 = 8.;
+// The first enemy of a run is always the same, to ease the player in
+pub const FIRST_ENEMY_BLUEPRINT: &str = "shieldy";
@@ -1,5 +1,10 @@
+use bevy::prelude::*;
+
 #[derive(Debug)]
-pub struct EnemyKilled;
+pub struct EnemyKilled {
+    // Where the ship was destroyed, none if it was only cleaned up far away
+    pub position: Option<Vec2>,
+}
 
 #[derive(Debug)]
 pub struct EnemySpawned;
@@ -0,0 +1,23 @@
+use super::{constants::BLUEPRINT_EXTENSION, EnemyBlueprint};
+use bevy::asset::{AssetLoader, BoxedFuture, LoadContext, LoadedAsset};
+
+#[derive(Default)]
+pub struct EnemyBlueprintLoader;
+
+impl AssetLoader for EnemyBlueprintLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let blueprint: EnemyBlueprint = ron::de::from_bytes(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(blueprint));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &[BLUEPRINT_EXTENSION]
+    }
+}
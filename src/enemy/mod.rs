@@ -1,13 +1,16 @@
 mod components;
 mod constants;
 mod events;
+mod loader;
 mod plugin;
+mod resources;
 mod spawners;
-pub(crate) mod systems;
+mod systems;
 
 pub use self::{
     components::*,
+    constants::ASSET_ENEMIES_FOLDER,
     events::{EnemyKilled, EnemySpawned},
-    plugin::{EnemyPlugin, EnemySystem},
-    systems::shoot_zappy_enemy_system,
+    plugin::{EnemyBlueprintPlugin, EnemyPlugin, EnemySystem},
+    resources::{EnemyBlueprint, EnemyBlueprints},
 };
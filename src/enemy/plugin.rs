@@ -1,13 +1,19 @@
 use crate::{
-    despawn_recursive::despawn_entities_recursive_system, player::PlayerSystem, schedule::GameState,
+    asset::AssetSystem,
+    despawn_recursive::despawn_entities_recursive_system,
+    physics::{on_physics_update, PhysicsStage, PhysicsSystem},
+    player::PlayerSystem,
+    schedule::GameState,
 };
 
 use super::{
+    loader::EnemyBlueprintLoader,
+    resources::BlueprintHandles,
     systems::{
-        check_enemy_death_system, clean_enemies_system, follow_player_in_range_system,
-        shoot_enemy_cannon_system, shoot_zappy_enemy_system, spawn_random_enemies_system,
+        chase_hostile_ships_system, check_enemy_death_system, clean_enemies_system,
+        load_enemy_blueprints_system, spawn_random_enemies_system, update_enemy_blueprints_system,
     },
-    EnemyKilled, EnemyRoot, EnemySpawned,
+    EnemyBlueprint, EnemyBlueprints, EnemyKilled, EnemyRoot, EnemySpawned,
 };
 use bevy::prelude::*;
 
@@ -20,15 +26,23 @@ impl Plugin for EnemyPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<EnemyKilled>()
             .add_event::<EnemySpawned>()
-            .add_system_set(
-                SystemSet::on_update(GameState::InGame)
+            // Once per physics step, so the enemies a replay meets don't depend on the frame rate
+            .add_system_set_to_stage(
+                PhysicsStage,
+                on_physics_update(GameState::InGame)
                     .label(EnemySystem)
+                    .after(PhysicsSystem::Propagate)
                     .with_system(check_enemy_death_system.after(PlayerSystem))
-                    .with_system(shoot_zappy_enemy_system)
-                    .with_system(shoot_enemy_cannon_system)
-                    .with_system(follow_player_in_range_system)
                     .with_system(clean_enemies_system.before(spawn_random_enemies_system))
-                    .with_system(spawn_random_enemies_system),
+                    // Every system that draws from the game RNG runs in a fixed order, so the
+                    // same seed always plays out the same
+                    .with_system(spawn_random_enemies_system.after(check_enemy_death_system)),
+            )
+            .add_system_set_to_stage(
+                PhysicsStage,
+                on_physics_update(GameState::InGame)
+                    .label(PhysicsSystem::Movement)
+                    .with_system(chase_hostile_ships_system),
             )
             .add_system_set(
                 SystemSet::on_exit(GameState::AfterInGame)
@@ -37,3 +51,23 @@ impl Plugin for EnemyPlugin {
             );
     }
 }
+
+/// Loads the enemy blueprints through the asset server and keeps them up to date.
+pub struct EnemyBlueprintPlugin;
+
+impl Plugin for EnemyBlueprintPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<EnemyBlueprint>()
+            .init_asset_loader::<EnemyBlueprintLoader>()
+            .init_resource::<EnemyBlueprints>()
+            .init_resource::<BlueprintHandles>()
+            .add_system_set(
+                SystemSet::on_enter(GameState::AssetLoading)
+                    .label(EnemySystem)
+                    .after(AssetSystem)
+                    .with_system(load_enemy_blueprints_system),
+            )
+            // Runs in every state, so edited files are picked up while playing
+            .add_system(update_enemy_blueprints_system.label(EnemySystem));
+    }
+}
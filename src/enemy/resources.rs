@@ -0,0 +1,110 @@
+use super::constants::BLUEPRINT_EXTENSION;
+use crate::{faction::Faction, random::GameRng, ron_file::load_ron_folder};
+use bevy::{prelude::*, reflect::TypeUuid};
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct BlueprintModule {
+    // Id of the module definition
+    pub id: String,
+    #[serde(default)]
+    pub random_rotation: bool,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct LootEntry {
+    // Id of the module definition
+    pub module: String,
+    // Chance between 0 and 1 that the module is dropped
+    pub chance: f32,
+}
+
+/// An enemy ship, loaded from a `.enemy.ron` file in the `enemies` asset folder.
+#[derive(Clone, Serialize, Deserialize, TypeUuid)]
+#[uuid = "0c8e4d52-7a3b-4f19-b6e2-9d41a5c7f803"]
+pub struct EnemyBlueprint {
+    pub id: String,
+    pub faction: Faction,
+    // Hit points of the core, the ship is destroyed when they run out
+    pub health: u32,
+    // Units per second
+    pub speed: f32,
+    // Relative chance of being picked when a random enemy spawns
+    pub spawn_weight: f32,
+    // Rows of cells from top to bottom, where every character is a key of `modules`
+    // and anything else is left empty
+    pub cells: Vec<String>,
+    pub modules: BTreeMap<char, BlueprintModule>,
+    // Dropped on top of the salvaged module
+    #[serde(default)]
+    pub loot: Vec<LootEntry>,
+}
+
+impl EnemyBlueprint {
+    /// The modules of the ship with their cell offset from the center.
+    pub fn module_cells(&self) -> impl Iterator<Item = (IVec2, &BlueprintModule)> {
+        let center_row = (self.cells.len() as i32 - 1) / 2;
+
+        self.cells.iter().enumerate().flat_map(move |(row, cells)| {
+            let center_column = (cells.chars().count() as i32 - 1) / 2;
+
+            cells.chars().enumerate().filter_map(move |(column, cell)| {
+                self.modules.get(&cell).map(|module| {
+                    (
+                        IVec2::new(column as i32 - center_column, center_row - row as i32),
+                        module,
+                    )
+                })
+            })
+        })
+    }
+}
+
+/// All enemy blueprints by id. Kept up to date with the blueprint assets, so
+/// changes to the files apply to every enemy spawned afterwards.
+#[derive(Default)]
+pub struct EnemyBlueprints {
+    // Ordered, so picking a random blueprint is deterministic for a given seed
+    blueprints: BTreeMap<String, EnemyBlueprint>,
+}
+
+impl EnemyBlueprints {
+    /// Reads the blueprints straight from disk, for when there is no asset server.
+    pub fn load(path: &str) -> Self {
+        let mut blueprints = Self::default();
+        for blueprint in load_ron_folder(path, BLUEPRINT_EXTENSION) {
+            blueprints.insert(blueprint);
+        }
+
+        blueprints
+    }
+
+    pub fn insert(&mut self, blueprint: EnemyBlueprint) {
+        self.blueprints.insert(blueprint.id.clone(), blueprint);
+    }
+
+    /// The blueprint with the given id, if it is loaded. Saves and hot reloaded files can refer
+    /// to ids that no longer exist.
+    pub fn get(&self, id: &str) -> Option<&EnemyBlueprint> {
+        self.blueprints.get(id)
+    }
+
+    pub fn remove(&mut self, id: &str) {
+        self.blueprints.remove(id);
+    }
+
+    pub fn choose(&self, rng: &mut GameRng) -> Option<&EnemyBlueprint> {
+        let blueprints: Vec<&EnemyBlueprint> = self.blueprints.values().collect();
+
+        blueprints
+            .choose_weighted(&mut **rng, |blueprint| blueprint.spawn_weight)
+            .ok()
+            .copied()
+    }
+}
+
+// Keeps the handles alive, so the blueprints stay loaded and can be hot reloaded
+#[derive(Default)]
+pub struct BlueprintHandles(pub Vec<HandleUntyped>);
@@ -1,358 +1,156 @@
-use super::constants::ENEMY_SPEED;
-use super::spawners::{spawn_boomy, spawn_shieldy, spawn_zappy};
-use super::{Enemy, EnemyKilled, EnemyRoot, EnemySpawned, EnemyType};
+use super::constants::{ASSET_ENEMIES_FOLDER, BLUEPRINT_FILES, FIRST_ENEMY_BLUEPRINT};
+use super::resources::BlueprintHandles;
+use super::spawners::spawn_enemy;
+use super::{EnemyBlueprint, EnemyBlueprints, EnemyKilled, EnemyRoot, EnemySpawned};
 use crate::asset::SpriteHandles;
-use crate::audio::{AudioEvent, AudioType};
-use crate::colors::COLOR_ENEMY;
-use crate::components::{Bullet, Cannon, Projectile, ShieldForcefield};
-use crate::nodes::{spawn_cannon_node, spawn_zapper_node};
+use crate::asset::{load_folder, LoadingAssets};
+use crate::components::{Health, Velocity};
+use crate::faction::Faction;
+use crate::module::{Module, ModuleDefinition, ModuleDefinitions};
+use crate::nodes::spawn_module_node;
 use crate::object::Object;
-use crate::player::{PlayerHistory, PlayerRoot};
+use crate::physics::PHYSICS_TIMESTEP;
+use crate::player::PlayerRoot;
+use crate::random::GameRng;
 use crate::stats::Stats;
-use crate::{
-    components::{Properties, Velocity, ZapEffect, Zapper},
-    events::Hit,
-    nodes::{spawn_empty_node, spawn_shield_node},
-    player::Player,
-};
-use bevy::prelude::*;
-use rand::Rng;
+use crate::window::{WINDOW_HEIGHT, WINDOW_WIDTH};
+use bevy::{asset::HandleId, prelude::*, utils::HashMap};
+use rand::{seq::SliceRandom, Rng};
 use std::f32::consts::TAU;
 
 pub fn check_enemy_death_system(
     mut commands: Commands,
-    sprite_handles: Res<SpriteHandles>,
-    mut query: Query<(&Properties, Entity, &EnemyRoot, &Transform), With<EnemyRoot>>,
+    module_definitions: Res<ModuleDefinitions>,
+    enemy_blueprints: Res<EnemyBlueprints>,
+    mut rng: ResMut<GameRng>,
+    query: Query<(&Health, Entity, &EnemyRoot, &Transform, Option<&Children>)>,
+    module_query: Query<&Module>,
     mut enemy_killed_events: EventWriter<EnemyKilled>,
 ) {
-    for (properties, entity, root, transform) in query.iter_mut() {
-        if properties.health == 0 {
-            enemy_killed_events.send(EnemyKilled);
-
-            match root.enemy_type {
-                EnemyType::Shieldy => {
-                    // Drop a shield and 2 debris
-                    let shield = spawn_shield_node(
-                        &mut commands,
-                        transform.translation,
-                        0.,
-                        sprite_handles.shield.clone(),
-                        sprite_handles.forcefield.clone(),
-                        ShieldForcefield {
-                            health: 20,
-                            cooldown: 3.,
-                            cooldown_timer: 0.,
-                        },
-                        false,
-                    );
+    for (core_health, entity, root, transform, children) in query.iter() {
+        // The ship is destroyed together with its core
+        if core_health.current == 0 {
+            enemy_killed_events.send(EnemyKilled {
+                position: Some(transform.translation.truncate()),
+            });
 
-                    commands
-                        .entity(shield)
-                        .insert(Velocity {
-                            x: rand::random::<f32>() * 2. - 1.,
-                            y: rand::random::<f32>() * 2. - 1.,
-                            rotation: rand::random::<f32>() * 0.1,
-                        })
-                        .insert(Object {});
-
-                    for _ in 0..2 {
-                        // Spawn debris
-                        let debris = spawn_empty_node(
-                            &mut commands,
-                            transform.translation,
-                            rand::random::<f32>() * TAU,
-                            sprite_handles.debris.clone(),
-                        );
-
-                        commands.entity(debris).insert(Object {}).insert(Velocity {
-                            x: rand::random::<f32>() - 0.5,
-                            y: rand::random::<f32>() - 0.5,
-                            rotation: rand::random::<f32>() * 0.2,
-                        });
-                    }
-                }
-                EnemyType::Zappy => {
-                    // Drop a zapper and 2 debris
-                    let shield = spawn_zapper_node(
-                        &mut commands,
-                        transform.translation,
-                        0.,
-                        sprite_handles.zapper.clone(),
-                        Zapper {
-                            damage: 10,
-                            fire_rate: 1.,
-                            cooldown_timer: 0.,
-                            range: 100.,
-                        },
-                    );
+            // Drop one of the ship's modules, picked by their drop weights. This happens no matter
+            // who destroyed the ship, so the player can scavenge the fights between other factions
+            let droppable: Vec<&ModuleDefinition> = children
+                .iter()
+                .flat_map(|children| children.iter())
+                .filter_map(|child| module_query.get(*child).ok())
+                .filter_map(|module| module_definitions.get(&module.id))
+                .filter(|definition| definition.drop_weight > 0.)
+                .collect();
+            if let Ok(dropped) =
+                droppable.choose_weighted(&mut **rng, |definition| definition.drop_weight)
+            {
+                let id = dropped.salvage.as_deref().unwrap_or(&dropped.id);
+                let module = spawn_module_node(
+                    &mut commands,
+                    &module_definitions,
+                    id,
+                    transform.translation,
+                    0.,
+                    false,
+                );
 
+                if let Some(module) = module {
                     commands
-                        .entity(shield)
+                        .entity(module)
                         .insert(Velocity {
-                            x: rand::random::<f32>() * 2. - 1.,
-                            y: rand::random::<f32>() * 2. - 1.,
-                            rotation: rand::random::<f32>() * 0.1,
+                            x: rng.gen::<f32>() * 120. - 60.,
+                            y: rng.gen::<f32>() * 120. - 60.,
+                            rotation: rng.gen::<f32>() * 6.,
                         })
                         .insert(Object {});
-
-                    for _ in 0..2 {
-                        // Spawn debris
-                        let debris = spawn_empty_node(
-                            &mut commands,
-                            transform.translation,
-                            rand::random::<f32>() * TAU,
-                            sprite_handles.debris.clone(),
-                        );
-
-                        commands.entity(debris).insert(Object {}).insert(Velocity {
-                            x: rand::random::<f32>() - 0.5,
-                            y: rand::random::<f32>() - 0.5,
-                            rotation: rand::random::<f32>() * 0.2,
-                        });
-                    }
-                }
-                EnemyType::Boomy => {
-                    // Drop a zapper and 2 debris
-                    let shield = spawn_cannon_node(
-                        &mut commands,
-                        transform.translation,
-                        0.,
-                        sprite_handles.cannon.clone(),
-                        Cannon {
-                            damage: 10,
-                            fire_rate: 1.,
-                            cooldown_timer: 0.,
-                            range: 100.,
-                        },
-                    );
-
-                    commands
-                        .entity(shield)
-                        .insert(Velocity {
-                            x: rand::random::<f32>() * 2. - 1.,
-                            y: rand::random::<f32>() * 2. - 1.,
-                            rotation: rand::random::<f32>() * 0.1,
-                        })
-                        .insert(Object {});
-
-                    for _ in 0..2 {
-                        // Spawn debris
-                        let debris = spawn_empty_node(
-                            &mut commands,
-                            transform.translation,
-                            rand::random::<f32>() * TAU,
-                            sprite_handles.debris.clone(),
-                        );
-
-                        commands.entity(debris).insert(Object {}).insert(Velocity {
-                            x: rand::random::<f32>() - 0.5,
-                            y: rand::random::<f32>() - 0.5,
-                            rotation: rand::random::<f32>() * 0.2,
-                        });
-                    }
                 }
             }
-            commands.entity(entity).despawn_recursive();
-        }
-    }
-}
-
-pub fn shoot_zappy_enemy_system(
-    mut commands: Commands,
-    time: Res<Time>,
-    mut event_hit: EventWriter<Hit>,
-    mut event_audio: EventWriter<AudioEvent>,
-
-    mut zapper_query: Query<(&GlobalTransform, &mut Zapper), With<Enemy>>,
-    shootable_query: Query<(&GlobalTransform, Entity, &Parent), With<Player>>,
-    player_history: Res<PlayerHistory>,
-) {
-    for (zapper_transform, mut zapper_stats) in zapper_query.iter_mut() {
-        if zapper_stats.cooldown_timer > 0. {
-            zapper_stats.cooldown_timer -= time.delta_seconds();
-        } else {
-            let zapper_compute = zapper_transform.compute_transform();
-            let distance_to_target = player_history
-                .target_position
-                .distance(zapper_compute.translation);
 
-            // If past position is in range
-            if distance_to_target < zapper_stats.range {
-                // Iterate over all shootable entities
-                zapper_stats.cooldown_timer = zapper_stats.fire_rate;
-                for (shootable_transform, _shootable_entity, shootable_parent) in
-                    shootable_query.iter()
-                {
-                    // If one of the shootable targets is close to the past position
-                    let shootable_compute = shootable_transform.compute_transform();
-
-                    let distance_shootable_past = player_history
-                        .target_position
-                        .distance(shootable_compute.translation);
-
-                    if distance_shootable_past < 4. {
-                        event_hit.send(Hit {
-                            target: shootable_parent.get(),
-                            damage: zapper_stats.damage,
-                        });
-                        event_audio.send(AudioEvent(AudioType::Laser));
-                        event_audio.send(AudioEvent(AudioType::Hit));
-
-                        // Draw squares, interpolated between the two points
-                        for i in 1..distance_to_target as i32 {
-                            let t = i as f32 / distance_to_target.floor();
-                            let x = zapper_compute.translation.x
-                                + (shootable_transform.compute_transform().translation.x
-                                    - zapper_compute.translation.x)
-                                    * t;
-                            let y = zapper_compute.translation.y
-                                + (shootable_transform.compute_transform().translation.y
-                                    - zapper_compute.translation.y)
-                                    * t;
-                            commands
-                                .spawn()
-                                .insert_bundle(SpriteBundle {
-                                    transform: Transform {
-                                        translation: Vec3::new(x, y, 0.),
-                                        scale: Vec3::new(2., 2., 0.),
-                                        ..default()
-                                    },
-                                    sprite: Sprite {
-                                        color: COLOR_ENEMY,
-                                        ..default()
-                                    },
-                                    ..default()
-                                })
-                                .insert(ZapEffect);
-                        }
-
-                        // Only one shot per cooldown
-                        return;
-                    }
+            // And whatever else the blueprint drops
+            let loot = enemy_blueprints
+                .get(&root.blueprint)
+                .map_or(&[][..], |blueprint| blueprint.loot.as_slice());
+            for loot in loot {
+                if rng.gen::<f32>() >= loot.chance {
+                    continue;
                 }
 
-                // If nothing is hit just shoot towards the past position
-                event_audio.send(AudioEvent(AudioType::Laser));
-                event_audio.send(AudioEvent(AudioType::Hit));
+                let module = spawn_module_node(
+                    &mut commands,
+                    &module_definitions,
+                    &loot.module,
+                    transform.translation,
+                    rng.gen::<f32>() * TAU,
+                    false,
+                );
 
-                // Draw squares, interpolated between the two points
-                for i in 1..distance_to_target as i32 {
-                    let t = i as f32 / distance_to_target.floor();
-                    let x = zapper_compute.translation.x
-                        + (player_history.target_position.x - zapper_compute.translation.x) * t;
-                    let y = zapper_compute.translation.y
-                        + (player_history.target_position.y - zapper_compute.translation.y) * t;
-                    commands
-                        .spawn()
-                        .insert_bundle(SpriteBundle {
-                            transform: Transform {
-                                translation: Vec3::new(x, y, 0.),
-                                scale: Vec3::new(2., 2., 0.),
-                                ..default()
-                            },
-                            sprite: Sprite {
-                                color: COLOR_ENEMY,
-                                ..default()
-                            },
-                            ..default()
-                        })
-                        .insert(ZapEffect);
+                if let Some(module) = module {
+                    commands.entity(module).insert(Object {}).insert(Velocity {
+                        x: rng.gen::<f32>() * 60. - 30.,
+                        y: rng.gen::<f32>() * 60. - 30.,
+                        rotation: rng.gen::<f32>() * 12.,
+                    });
                 }
             }
+
+            commands.entity(entity).despawn_recursive();
         }
     }
 }
 
-pub fn shoot_enemy_cannon_system(
-    mut commands: Commands,
-    time: Res<Time>,
-    mut event_audio: EventWriter<AudioEvent>,
-    mut cannon_query: Query<(&GlobalTransform, &mut Cannon), With<Enemy>>,
-    shootable_query: Query<(&GlobalTransform, Entity, &Parent), With<Player>>,
+// Every enemy ship goes after the closest ship it is hostile to, which isn't always the player
+#[allow(clippy::type_complexity)]
+pub fn chase_hostile_ships_system(
+    mut ship_query: Query<
+        (Entity, &mut Transform, &Faction, Option<&EnemyRoot>),
+        Or<(With<PlayerRoot>, With<EnemyRoot>)>,
+    >,
+    enemy_blueprints: Res<EnemyBlueprints>,
 ) {
-    // The same as player, just Player and Enemy switched
-    for (cannon_transform, mut cannon_stats) in cannon_query.iter_mut() {
-        if cannon_stats.cooldown_timer > 0. {
-            cannon_stats.cooldown_timer -= time.delta_seconds();
-        } else {
-            for (shootable_transform, _shootable_entity, _shootable_parent) in
-                shootable_query.iter()
-            {
-                let distance = cannon_transform
-                    .compute_transform()
-                    .translation
-                    .distance(shootable_transform.compute_transform().translation);
-                // If there is a hit
-                if distance < cannon_stats.range {
-                    cannon_stats.cooldown_timer = cannon_stats.fire_rate;
-
-                    event_audio.send(AudioEvent(AudioType::Explosion));
+    let ships: Vec<(Entity, Vec3, Faction)> = ship_query
+        .iter()
+        .map(|(entity, transform, faction, _)| (entity, transform.translation, *faction))
+        .collect();
 
-                    let velocity_x: f32 = (shootable_transform.compute_transform().translation.x
-                        - cannon_transform.compute_transform().translation.x)
-                        / distance;
-                    let velocity_y: f32 = (shootable_transform.compute_transform().translation.y
-                        - cannon_transform.compute_transform().translation.y)
-                        / distance;
+    for (entity, mut transform, faction, enemy_root) in ship_query.iter_mut() {
+        // Ships whose blueprint is gone don't know how fast they are, so they drift
+        let speed = match enemy_root.and_then(|root| enemy_blueprints.get(&root.blueprint)) {
+            Some(blueprint) => blueprint.speed,
+            None => continue,
+        };
 
-                    // Shoot a bullet
-                    commands
-                        .spawn()
-                        .insert_bundle(SpriteBundle {
-                            transform: Transform {
-                                translation: cannon_transform.compute_transform().translation,
-                                scale: Vec3::new(2., 2., 0.),
-                                ..default()
-                            },
-                            sprite: Sprite {
-                                color: COLOR_ENEMY,
-                                ..default()
-                            },
-                            ..default()
-                        })
-                        .insert(Projectile {})
-                        .insert(Bullet {
-                            damage: cannon_stats.damage,
-                            enemy: true,
-                        })
-                        .insert(Velocity {
-                            x: velocity_x * 2.,
-                            y: velocity_y * 2.,
-                            rotation: 0.,
-                        });
-
-                    // Only one shot per cooldown
-                    break;
-                }
-            }
-        }
-    }
-}
+        let target = ships
+            .iter()
+            .filter(|(other, _, other_faction)| {
+                *other != entity && faction.is_hostile_to(*other_faction)
+            })
+            .map(|(_, position, _)| *position)
+            .min_by(|a, b| {
+                a.distance_squared(transform.translation)
+                    .total_cmp(&b.distance_squared(transform.translation))
+            });
 
-pub fn follow_player_in_range_system(
-    player_query: Query<&Transform, With<PlayerRoot>>,
-    mut enemy_query: Query<&mut Transform, (With<EnemyRoot>, Without<PlayerRoot>)>,
-    time: Res<Time>,
-) {
-    for player_transform in player_query.iter() {
-        for mut enemy_transform in enemy_query.iter_mut() {
-            let distance = player_transform
-                .translation
-                .distance(enemy_transform.translation);
+        if let Some(target) = target {
+            let distance = target.distance(transform.translation);
             if distance > 8. {
-                let direction =
-                    (player_transform.translation - enemy_transform.translation).normalize();
-                enemy_transform.translation += direction * 0.6 * ENEMY_SPEED * time.delta_seconds();
+                let direction = (target - transform.translation).normalize();
+                transform.translation += direction * speed * PHYSICS_TIMESTEP;
             }
         }
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn spawn_random_enemies_system(
-    commands: Commands,
+    mut commands: Commands,
     stats: Res<Stats>,
-    windows: Res<Windows>,
+    windows: Option<Res<Windows>>,
     sprite_handles: Res<SpriteHandles>,
+    module_definitions: Res<ModuleDefinitions>,
+    enemy_blueprints: Res<EnemyBlueprints>,
+    mut rng: ResMut<GameRng>,
     player_query: Query<(&PlayerRoot, &Transform), With<PlayerRoot>>,
     mut enemy_spawned_event: EventWriter<EnemySpawned>,
 ) {
@@ -363,51 +161,65 @@ pub fn spawn_random_enemies_system(
         // Spawn a random enemy just outside of the screen
         // Due to the camera zoom, the side of the screen is actually not the side of the viewport
         let scale = (0.25 + (0.01 * (player_root.dist / 8.0))) / 0.25;
-        let half_width = (windows.get_primary().unwrap().width() as f32 / 7.) * scale;
-        let half_height = (windows.get_primary().unwrap().height() as f32 / 7.) * scale;
-        let screen_side = rand::thread_rng().gen_range(0..4);
+        // Without a window (e.g. in headless mode) fall back to the default window size
+        let (width, height) = windows
+            .as_ref()
+            .and_then(|windows| windows.get_primary())
+            .map_or((WINDOW_WIDTH, WINDOW_HEIGHT), |window| {
+                (window.width(), window.height())
+            });
+        let half_width = (width / 7.) * scale;
+        let half_height = (height / 7.) * scale;
+        let screen_side = rng.gen_range(0..4);
         let offset = 40.;
         let position = player_transform
             + match screen_side {
                 // Left
                 0 => Vec3::new(
                     -half_width - offset,
-                    rand::thread_rng().gen_range(-half_height..half_height),
+                    rng.gen_range(-half_height..half_height),
                     0.,
                 ),
                 // Right
                 1 => Vec3::new(
                     half_width + offset,
-                    rand::thread_rng().gen_range(-half_height..half_height),
+                    rng.gen_range(-half_height..half_height),
                     0.,
                 ),
                 // Up
                 2 => Vec3::new(
-                    rand::thread_rng().gen_range(-half_width..half_width),
+                    rng.gen_range(-half_width..half_width),
                     half_height + offset,
                     0.,
                 ),
                 // Down
                 _ => Vec3::new(
-                    rand::thread_rng().gen_range(-half_width..half_width),
+                    rng.gen_range(-half_width..half_width),
                     -half_height - offset,
                     0.,
                 ),
             };
 
-        if stats.kills == 0 {
-            spawn_shieldy(commands, sprite_handles, position);
+        // The first enemy is always the same one, as long as its blueprint is there
+        let first_blueprint = if stats.kills == 0 {
+            enemy_blueprints.get(FIRST_ENEMY_BLUEPRINT)
         } else {
-            // Spawn a random enemy
-            let enemy_type = rand::thread_rng().gen_range(0..3);
-            match enemy_type {
-                0 => spawn_shieldy(commands, sprite_handles, position),
-                1 => spawn_boomy(commands, sprite_handles, position),
-                _ => spawn_zappy(commands, sprite_handles, position),
-            }
-        }
+            None
+        };
+        // Otherwise spawn a random enemy
+        let blueprint = first_blueprint.or_else(|| enemy_blueprints.choose(&mut rng));
 
-        enemy_spawned_event.send(EnemySpawned);
+        if let Some(blueprint) = blueprint {
+            spawn_enemy(
+                &mut commands,
+                &sprite_handles,
+                &module_definitions,
+                blueprint,
+                &mut rng,
+                position,
+            );
+            enemy_spawned_event.send(EnemySpawned);
+        }
     }
 }
 
@@ -425,8 +237,75 @@ pub fn clean_enemies_system(
                 .distance(enemy_transform.translation);
             if distance > 3000. {
                 commands.entity(enemy_entity).despawn_recursive();
-                enemy_killed_events.send(EnemyKilled);
+                enemy_killed_events.send(EnemyKilled { position: None });
             }
         }
     }
 }
+
+pub fn load_enemy_blueprints_system(
+    asset_server: Res<AssetServer>,
+    mut blueprint_handles: ResMut<BlueprintHandles>,
+    mut loading_assets: ResMut<LoadingAssets>,
+) {
+    match load_folder(&asset_server, ASSET_ENEMIES_FOLDER, BLUEPRINT_FILES) {
+        Ok(handles) => {
+            loading_assets.extend(handles.iter().cloned());
+            blueprint_handles.0 = handles;
+        }
+        // The game still runs, just without any enemies to spawn
+        Err(err) => error!("Failed to load enemy blueprints: {:?}", err),
+    }
+}
+
+// Like the module definitions, the ids are remembered by handle
+pub fn update_enemy_blueprints_system(
+    mut asset_events: EventReader<AssetEvent<EnemyBlueprint>>,
+    assets: Res<Assets<EnemyBlueprint>>,
+    mut enemy_blueprints: ResMut<EnemyBlueprints>,
+    mut ids: Local<HashMap<HandleId, String>>,
+) {
+    for event in asset_events.iter() {
+        match event {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle } => {
+                if let Some(blueprint) = assets.get(handle) {
+                    info!("Loaded enemy blueprint {}", blueprint.id);
+                    if let Some(old_id) = ids.insert(handle.id, blueprint.id.clone()) {
+                        enemy_blueprints.remove(&old_id);
+                    }
+                    enemy_blueprints.insert(blueprint.clone());
+                }
+            }
+            AssetEvent::Removed { handle } => {
+                if let Some(id) = ids.remove(&handle.id) {
+                    info!("Removed enemy blueprint {}", id);
+                    enemy_blueprints.remove(&id);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enemy::constants::BLUEPRINT_EXTENSION;
+    use std::{fs, path::Path};
+
+    #[test]
+    fn every_blueprint_file_is_listed_for_the_web() {
+        let folder = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("assets")
+            .join(ASSET_ENEMIES_FOLDER);
+        let mut files: Vec<String> = fs::read_dir(folder)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .filter(|name| name.ends_with(BLUEPRINT_EXTENSION))
+            .collect();
+        files.sort();
+        let mut listed = BLUEPRINT_FILES.to_vec();
+        listed.sort_unstable();
+
+        assert_eq!(files, listed);
+    }
+}
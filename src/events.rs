@@ -1,6 +1,40 @@
+use crate::components::DamageType;
 use bevy::prelude::*;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WeaponKind {
+    Zapper,
+    Cannon,
+}
+
 pub struct Hit {
+    // Before the resistances of the target
     pub damage: u32,
+    pub damage_type: DamageType,
     pub target: Entity,
+    // The weapon node that fired, if it's still known
+    pub source: Option<Entity>,
+    // Where the hit landed, in world space
+    pub position: Vec2,
+    pub weapon: WeaponKind,
+    // Velocity pushed onto the struck ship, spread over its size
+    pub impulse: Vec2,
+}
+
+// A hit after the resistances of the node it struck, the damage that node's health lost.
+// Sent once per hit that landed, so everything that counts damage counts the same.
+pub struct DamageApplied {
+    pub damage: u32,
+    pub damage_type: DamageType,
+    pub target: Entity,
+    pub source: Option<Entity>,
+    pub position: Vec2,
+    pub weapon: WeaponKind,
+}
+
+// A hit a forcefield took instead of the ship behind it
+pub struct ForcefieldHit {
+    // After the resistances of the forcefield
+    pub damage: u32,
+    pub position: Vec2,
 }
@@ -0,0 +1,78 @@
+use crate::colors::{COLOR_DRONES, COLOR_ENEMY, COLOR_PLAYER, COLOR_SALVAGERS};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// The side a node or projectile fights on. Weapons only target and hit hostile factions.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Faction {
+    Player,
+    Pirates,
+    Drones,
+    Salvagers,
+}
+
+// The pairs of factions that fight each other, in both directions.
+// Everyone is after the player, but pirates and salvagers leave each other alone.
+const HOSTILITIES: [(Faction, Faction); 5] = [
+    (Faction::Player, Faction::Pirates),
+    (Faction::Player, Faction::Drones),
+    (Faction::Player, Faction::Salvagers),
+    (Faction::Pirates, Faction::Drones),
+    (Faction::Drones, Faction::Salvagers),
+];
+
+impl Faction {
+    pub fn is_hostile_to(self, other: Faction) -> bool {
+        HOSTILITIES
+            .iter()
+            .any(|&(a, b)| (a == self && b == other) || (a == other && b == self))
+    }
+
+    // Color of the shots of the faction
+    pub fn color(self) -> Color {
+        match self {
+            Faction::Player => COLOR_PLAYER,
+            Faction::Pirates => COLOR_ENEMY,
+            Faction::Drones => COLOR_DRONES,
+            Faction::Salvagers => COLOR_SALVAGERS,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FACTIONS: [Faction; 4] = [
+        Faction::Player,
+        Faction::Pirates,
+        Faction::Drones,
+        Faction::Salvagers,
+    ];
+
+    #[test]
+    fn everyone_is_hostile_to_the_player() {
+        for faction in FACTIONS.iter().skip(1) {
+            assert!(Faction::Player.is_hostile_to(*faction));
+        }
+    }
+
+    #[test]
+    fn hostility_goes_both_ways() {
+        for a in FACTIONS {
+            for b in FACTIONS {
+                assert_eq!(a.is_hostile_to(b), b.is_hostile_to(a), "{:?} {:?}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn factions_leave_themselves_and_their_allies_alone() {
+        for faction in FACTIONS {
+            assert!(!faction.is_hostile_to(faction));
+        }
+        assert!(!Faction::Pirates.is_hostile_to(Faction::Salvagers));
+        assert!(Faction::Pirates.is_hostile_to(Faction::Drones));
+        assert!(Faction::Drones.is_hostile_to(Faction::Salvagers));
+    }
+}
@@ -0,0 +1,202 @@
+use bevy::{
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+use std::f32::consts::FRAC_PI_2;
+
+// Distance between the centers of two neighbouring cells, the size of a node sprite
+pub const GRID_CELL_SIZE: f32 = 8.;
+
+const NEIGHBOUR_OFFSETS: [IVec2; 4] = [
+    IVec2::new(1, 0),
+    IVec2::new(-1, 0),
+    IVec2::new(0, 1),
+    IVec2::new(0, -1),
+];
+
+/// The lattice a ship is built on, in the space of its root. Tracks which node
+/// occupies which cell, so nodes can't overlap and their neighbours can be looked up.
+#[derive(Component, Default)]
+pub struct ShipGrid {
+    cells: HashMap<IVec2, Entity>,
+}
+
+/// The cell of its ship's grid a node occupies.
+#[derive(Component, Clone, Copy)]
+pub struct GridCell(pub IVec2);
+
+impl ShipGrid {
+    pub fn cell_at(position: Vec2) -> IVec2 {
+        (position / GRID_CELL_SIZE).round().as_ivec2()
+    }
+
+    pub fn cell_position(cell: IVec2) -> Vec2 {
+        cell.as_vec2() * GRID_CELL_SIZE
+    }
+
+    // Rotations on the grid are quarter turns, so the nodes tile
+    pub fn snap_rotation(rotation: f32) -> f32 {
+        (rotation / FRAC_PI_2).round() * FRAC_PI_2
+    }
+
+    pub fn get(&self, cell: IVec2) -> Option<Entity> {
+        self.cells.get(&cell).copied()
+    }
+
+    pub fn is_occupied(&self, cell: IVec2) -> bool {
+        self.cells.contains_key(&cell)
+    }
+
+    pub fn occupy(&mut self, cell: IVec2, entity: Entity) {
+        self.cells.insert(cell, entity);
+    }
+
+    pub fn free(&mut self, cell: IVec2) -> Option<Entity> {
+        self.cells.remove(&cell)
+    }
+
+    /// Frees the cells of all nodes that don't pass the filter, and tells whether there were any.
+    pub fn retain(&mut self, mut filter: impl FnMut(Entity) -> bool) -> bool {
+        let count = self.cells.len();
+        self.cells.retain(|_, entity| filter(*entity));
+        self.cells.len() != count
+    }
+
+    pub fn cells(&self) -> impl Iterator<Item = (IVec2, Entity)> + '_ {
+        self.cells.iter().map(|(cell, entity)| (*cell, *entity))
+    }
+
+    /// The occupied cells sharing an edge with `cell`.
+    pub fn neighbours(&self, cell: IVec2) -> impl Iterator<Item = (IVec2, Entity)> + '_ {
+        NEIGHBOUR_OFFSETS.iter().filter_map(move |offset| {
+            let neighbour = cell + *offset;
+            self.get(neighbour).map(|entity| (neighbour, entity))
+        })
+    }
+
+    /// All occupied cells that can't be reached from `start` by going over neighbours,
+    /// sorted so they are always handled in the same order.
+    pub fn disconnected_from(&self, start: IVec2) -> Vec<(IVec2, Entity)> {
+        let mut connected = HashSet::default();
+        let mut open = Vec::new();
+        if self.is_occupied(start) {
+            connected.insert(start);
+            open.push(start);
+        }
+
+        // Flood fill from the start
+        while let Some(cell) = open.pop() {
+            for (neighbour, _) in self.neighbours(cell) {
+                if connected.insert(neighbour) {
+                    open.push(neighbour);
+                }
+            }
+        }
+
+        let mut disconnected: Vec<_> = self
+            .cells()
+            .filter(|(cell, _)| !connected.contains(cell))
+            .collect();
+        disconnected.sort_by_key(|(cell, _)| (cell.x, cell.y));
+        disconnected
+    }
+
+    /// The free cell closest to `position` that is next to an occupied one, if there is
+    /// one around the cell `position` falls into.
+    pub fn nearest_free_cell(&self, position: Vec2) -> Option<IVec2> {
+        let center = Self::cell_at(position);
+
+        (-1..=1)
+            .flat_map(|x| (-1..=1).map(move |y| center + IVec2::new(x, y)))
+            .filter(|cell| !self.is_occupied(*cell) && self.neighbours(*cell).next().is_some())
+            .min_by(|a, b| {
+                Self::cell_position(*a)
+                    .distance_squared(position)
+                    .total_cmp(&Self::cell_position(*b).distance_squared(position))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A grid with a node in each of the cells
+    fn grid(cells: &[(i32, i32)]) -> ShipGrid {
+        let mut grid = ShipGrid::default();
+        for (index, (x, y)) in cells.iter().enumerate() {
+            grid.occupy(IVec2::new(*x, *y), Entity::from_raw(index as u32));
+        }
+        grid
+    }
+
+    #[test]
+    fn cells_are_occupied_until_freed() {
+        let mut grid = grid(&[(0, 0)]);
+        let cell = IVec2::new(1, 0);
+
+        assert!(!grid.is_occupied(cell));
+        grid.occupy(cell, Entity::from_raw(7));
+        assert_eq!(grid.get(cell), Some(Entity::from_raw(7)));
+        assert_eq!(grid.free(cell), Some(Entity::from_raw(7)));
+        assert!(!grid.is_occupied(cell));
+        assert_eq!(grid.free(cell), None);
+    }
+
+    #[test]
+    fn retain_frees_the_cells_of_removed_nodes() {
+        let mut grid = grid(&[(0, 0), (1, 0)]);
+
+        assert!(!grid.retain(|_| true));
+        assert!(grid.retain(|entity| entity != Entity::from_raw(1)));
+        assert!(grid.is_occupied(IVec2::new(0, 0)));
+        assert!(!grid.is_occupied(IVec2::new(1, 0)));
+    }
+
+    #[test]
+    fn positions_round_to_the_nearest_cell() {
+        assert_eq!(ShipGrid::cell_at(Vec2::new(3.9, -4.1)), IVec2::new(0, -1));
+        assert_eq!(
+            ShipGrid::cell_at(ShipGrid::cell_position(IVec2::new(-2, 5))),
+            IVec2::new(-2, 5)
+        );
+    }
+
+    #[test]
+    fn nearest_free_cell_is_next_to_the_ship() {
+        let grid = grid(&[(0, 0), (1, 0)]);
+
+        // Right of the ship, closer to the upper row
+        assert_eq!(
+            grid.nearest_free_cell(Vec2::new(17., 3.)),
+            Some(IVec2::new(2, 0))
+        );
+        // Taken cells are skipped for the closest free one
+        assert_eq!(
+            grid.nearest_free_cell(Vec2::new(1., 3.)),
+            Some(IVec2::new(0, 1))
+        );
+        // Diagonals don't count as next to the ship
+        assert_eq!(grid.nearest_free_cell(Vec2::new(-17., -17.)), None);
+        assert_eq!(grid.nearest_free_cell(Vec2::new(40., 0.)), None);
+    }
+
+    #[test]
+    fn nodes_cut_off_from_the_core_are_disconnected() {
+        // A line from the core with a gap, and a node only touching it diagonally
+        let grid = grid(&[(0, 0), (1, 0), (3, 0), (4, 0), (2, 1)]);
+
+        let disconnected: Vec<IVec2> = grid
+            .disconnected_from(IVec2::ZERO)
+            .into_iter()
+            .map(|(cell, _)| cell)
+            .collect();
+        assert_eq!(
+            disconnected,
+            vec![IVec2::new(2, 1), IVec2::new(3, 0), IVec2::new(4, 0)]
+        );
+        assert_eq!(grid.disconnected_from(IVec2::new(3, 0)).len(), 3);
+        // Without a start everything is disconnected
+        assert_eq!(grid.disconnected_from(IVec2::new(9, 9)).len(), 5);
+    }
+}
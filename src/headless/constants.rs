@@ -0,0 +1,2 @@
+// Simulation
+pub const SIMULATION_TIMESTEP: f32 = 1.0 / 60.0;
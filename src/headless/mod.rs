@@ -0,0 +1,6 @@
+mod constants;
+mod plugin;
+mod resources;
+mod systems;
+
+pub use self::plugin::{HeadlessPlugin, HeadlessSystem};
@@ -0,0 +1,97 @@
+use super::{
+    constants::SIMULATION_TIMESTEP,
+    resources::{SimulatedClock, SimulationProgress},
+    systems::{
+        advance_simulated_clock_system, count_simulation_frames_system, restart_simulation_system,
+        start_simulation_system,
+    },
+};
+use crate::{
+    asset::SpriteHandles,
+    audio::{AudioEvent, PriorityAudioEvent},
+    enemy::{EnemyBlueprints, ASSET_ENEMIES_FOLDER},
+    module::{ModuleDefinitions, ASSET_MODULES_FOLDER},
+    schedule::GameState,
+};
+use bevy::{
+    asset::AssetServerSettings,
+    hierarchy::HierarchyPlugin,
+    input::InputPlugin,
+    log::LogPlugin,
+    prelude::*,
+    time::{FixedTimesteps, TimePlugin},
+    transform::TransformPlugin,
+    utils::Duration,
+};
+use std::{env, path::PathBuf};
+
+// Found the same way as by the asset server, so it doesn't depend on the working directory
+fn default_asset_folder() -> PathBuf {
+    let root = env::var("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .or_else(|_| {
+            env::current_exe().map(|exe| exe.parent().map(PathBuf::from).unwrap_or_default())
+        })
+        .unwrap_or_default();
+    root.join(AssetServerSettings::default().asset_folder)
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash, SystemLabel)]
+pub struct HeadlessSystem;
+
+/// Replaces the window, renderer and audio with a simulated clock, so the gameplay
+/// plugins can run on machines without a display.
+pub struct HeadlessPlugin {
+    /// Exit after this many frames, or run forever if `None`.
+    pub frames: Option<u32>,
+    /// The asset folder to read the definitions from, or the one the asset server would use
+    /// if `None`.
+    pub assets: Option<String>,
+}
+
+impl Plugin for HeadlessPlugin {
+    fn build(&self, app: &mut App) {
+        let asset_folder = match &self.assets {
+            Some(assets) => PathBuf::from(assets),
+            None => default_asset_folder(),
+        };
+
+        // The `TimePlugin` follows the wall clock, which we replace with our own
+        app.add_plugins_with(MinimalPlugins, |group| group.disable::<TimePlugin>())
+            .add_plugin(LogPlugin)
+            .add_plugin(TransformPlugin)
+            .add_plugin(HierarchyPlugin)
+            .add_plugin(InputPlugin)
+            .init_resource::<Time>()
+            .init_resource::<FixedTimesteps>()
+            .insert_resource(SimulatedClock::new(Duration::from_secs_f32(
+                SIMULATION_TIMESTEP,
+            )))
+            .insert_resource(SimulationProgress {
+                frame_limit: self.frames,
+                ..default()
+            })
+            // Resources that are usually provided by the asset and audio plugins
+            .insert_resource(SpriteHandles::default())
+            .insert_resource(ModuleDefinitions::load(
+                &asset_folder.join(ASSET_MODULES_FOLDER).to_string_lossy(),
+            ))
+            .insert_resource(EnemyBlueprints::load(
+                &asset_folder.join(ASSET_ENEMIES_FOLDER).to_string_lossy(),
+            ))
+            .add_event::<AudioEvent>()
+            .add_event::<PriorityAudioEvent>()
+            .add_system_to_stage(CoreStage::First, advance_simulated_clock_system)
+            .add_system(count_simulation_frames_system)
+            .add_system_set(
+                SystemSet::on_update(GameState::AssetLoading)
+                    .label(HeadlessSystem)
+                    .with_system(start_simulation_system),
+            )
+            .add_system_set(
+                SystemSet::on_enter(GameState::EndScreen)
+                    .label(HeadlessSystem)
+                    .with_system(restart_simulation_system),
+            );
+    }
+}
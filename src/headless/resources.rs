@@ -0,0 +1,30 @@
+use bevy::utils::{Duration, Instant};
+
+/// A clock that advances by a fixed step every frame instead of following the wall clock.
+pub struct SimulatedClock {
+    pub start: Instant,
+    pub elapsed: Duration,
+    pub step: Duration,
+}
+
+impl SimulatedClock {
+    pub fn new(step: Duration) -> Self {
+        Self {
+            start: Instant::now(),
+            elapsed: Duration::ZERO,
+            step,
+        }
+    }
+
+    pub fn tick(&mut self) -> Instant {
+        self.elapsed += self.step;
+        self.start + self.elapsed
+    }
+}
+
+#[derive(Default)]
+pub struct SimulationProgress {
+    pub frames: u32,
+    pub frame_limit: Option<u32>,
+    pub runs: u32,
+}
@@ -0,0 +1,55 @@
+use super::resources::{SimulatedClock, SimulationProgress};
+use crate::{
+    random::GameRng,
+    schedule::{GameState, ScheduleQueue},
+    stats::Stats,
+};
+use bevy::{app::AppExit, prelude::*};
+
+pub fn advance_simulated_clock_system(mut time: ResMut<Time>, mut clock: ResMut<SimulatedClock>) {
+    let instant = clock.tick();
+    time.update_with_instant(instant);
+}
+
+// There are no assets to load, so the game starts right away
+pub fn start_simulation_system(mut game_state: ResMut<State<GameState>>) {
+    game_state.set(GameState::BeforeInGame).unwrap();
+}
+
+pub fn count_simulation_frames_system(
+    mut progress: ResMut<SimulationProgress>,
+    stats: Res<Stats>,
+    mut app_exit_events: EventWriter<AppExit>,
+) {
+    progress.frames += 1;
+
+    if progress.frame_limit == Some(progress.frames) {
+        info!(
+            "Simulated {} frames over {} runs, current score: {}, kills: {}",
+            progress.frames, progress.runs, stats.score, stats.kills
+        );
+        app_exit_events.send(AppExit);
+    }
+}
+
+// Behaves like pressing the restart button on the end screen
+pub fn restart_simulation_system(
+    mut progress: ResMut<SimulationProgress>,
+    stats: Res<Stats>,
+    rng: Res<GameRng>,
+    mut game_state: ResMut<State<GameState>>,
+    mut schedule_queue: ResMut<ScheduleQueue>,
+) {
+    progress.runs += 1;
+    info!(
+        "Run {} with seed {} ended after {} frames, score: {}, kills: {}",
+        progress.runs,
+        rng.seed(),
+        progress.frames,
+        stats.score,
+        stats.kills
+    );
+
+    game_state.set(GameState::AfterEndScreen).unwrap();
+    schedule_queue.0.push_back(GameState::BeforeInGame);
+}
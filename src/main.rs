@@ -1,13 +1,22 @@
-use bevy::{prelude::*, render::texture::ImageSettings, sprite::Material2dPlugin};
+use bevy::{
+    asset::AssetServerSettings, prelude::*, render::texture::ImageSettings,
+    sprite::Material2dPlugin,
+};
 
 // Diagnostic imports
 // use bevy::diagnostic::EntityCountDiagnosticsPlugin;
 // use bevy_editor_pls::prelude::*;
 
+mod args;
+mod collision;
 mod components;
 mod despawn_recursive;
 mod events;
+mod faction;
+mod grid;
 mod nodes;
+mod ron_file;
+use args::Args;
 use events::*;
 use starfield::CustomMaterial;
 mod colors;
@@ -16,38 +25,121 @@ mod colors;
 mod asset;
 mod audio;
 mod camera;
+mod damage_number;
+mod effect;
 mod enemy;
+mod headless;
+mod module;
 mod object;
+mod particle;
+mod physics;
 mod player;
+mod random;
+mod replay;
+mod save;
 mod schedule;
+mod spatial;
 mod starfield;
 mod stats;
 mod ui;
+mod weapon;
 mod window;
 
-#[allow(clippy::type_complexity)]
 fn main() {
-    App::new()
-        // ----- Bevy -----
-        .add_event::<Hit>()
-        .insert_resource(ImageSettings::default_nearest())
-        // ----- Plugins -----
-        .add_plugin(window::WindowPlugin) // Has to be before the `DefaultPlugins`
-        .add_plugins(DefaultPlugins)
-        .add_plugin(asset::AssetPlugin)
-        .add_plugin(audio::AudioPlugin)
-        .add_plugin(Material2dPlugin::<CustomMaterial>::default())
-        .add_plugin(camera::CameraPlugin)
+    app(&Args::from_env()).run();
+}
+
+#[allow(clippy::type_complexity)]
+fn app(args: &Args) -> App {
+    let mut app = App::new();
+
+    // ----- Bevy -----
+    app.add_event::<Hit>()
+        .add_event::<DamageApplied>()
+        .add_event::<ForcefieldHit>();
+
+    if args.headless {
+        app.add_plugin(headless::HeadlessPlugin {
+            frames: args.frames,
+            assets: args.assets.clone(),
+        });
+    } else {
+        app.insert_resource(ImageSettings::default_nearest())
+            // Hot reload the module definitions
+            .insert_resource(AssetServerSettings {
+                watch_for_changes: true,
+                ..default()
+            })
+            // ----- Plugins -----
+            .add_plugin(window::WindowPlugin) // Has to be before the `DefaultPlugins`
+            .add_plugins(DefaultPlugins)
+            .add_plugin(asset::AssetPlugin)
+            .add_plugin(audio::AudioPlugin)
+            .add_plugin(module::ModulePlugin)
+            .add_plugin(enemy::EnemyBlueprintPlugin)
+            .add_plugin(Material2dPlugin::<CustomMaterial>::default())
+            .add_plugin(camera::CameraPlugin)
+            .add_plugin(particle::ParticlePlugin)
+            .add_plugin(damage_number::DamageNumberPlugin)
+            .add_plugin(starfield::StarfieldPlugin)
+            .add_plugin(ui::UiPlugin);
+    }
+
+    // ----- Gameplay -----
+    app.add_plugin(physics::PhysicsPlugin) // Has to be before the plugins that use its stage
+        .add_plugin(effect::EffectPlugin)
         .add_plugin(enemy::EnemyPlugin)
         .add_plugin(object::ObjectPlugin)
         .add_plugin(player::PlayerPlugin)
+        .add_plugin(random::RandomPlugin { seed: args.seed })
+        .add_plugin(replay::ReplayPlugin {
+            playback: args.replay.as_deref().and_then(replay::Replay::load),
+        })
+        .add_plugin(save::SavePlugin)
         .add_plugin(schedule::SchedulePlugin)
-        .add_plugin(starfield::StarfieldPlugin)
+        .add_plugin(spatial::SpatialPlugin)
         .add_plugin(stats::StatsPlugin)
-        .add_plugin(ui::UiPlugin)
-        // ----- Diagnostics -----
-        // .add_plugin(EditorPlugin)
-        // .add_plugin(EntityCountDiagnosticsPlugin)
-        // ----- Start -----
-        .run();
+        .add_plugin(weapon::WeaponPlugin);
+    // ----- Diagnostics -----
+    // app.add_plugin(EditorPlugin)
+    //     .add_plugin(EntityCountDiagnosticsPlugin);
+
+    app
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{enemy::EnemyRoot, player::PlayerRoot, random::GameRng, stats::Stats};
+    use rand::Rng;
+
+    // What a run looks like after a few seconds of simulated play
+    fn simulate(seed: u64) -> (u64, Vec<Vec3>, u32, u32) {
+        let mut app = app(&Args {
+            headless: true,
+            seed: Some(seed),
+            ..default()
+        });
+        for _ in 0..600 {
+            app.update();
+        }
+
+        let ships: Vec<Vec3> = app
+            .world
+            .query_filtered::<&Transform, Or<(With<PlayerRoot>, With<EnemyRoot>)>>()
+            .iter(&app.world)
+            .map(|transform| transform.translation)
+            .collect();
+        let stats = app.world.resource::<Stats>();
+        let (kills, damage_taken) = (stats.kills, stats.damage_taken);
+        // Only the same, if the generator was drawn from the same number of times
+        let next_draw = app.world.resource_mut::<GameRng>().gen::<u64>();
+
+        (next_draw, ships, kills, damage_taken)
+    }
+
+    #[test]
+    fn runs_with_the_same_seed_play_out_the_same() {
+        assert_eq!(simulate(7), simulate(7));
+    }
 }
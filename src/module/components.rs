@@ -0,0 +1,7 @@
+use bevy::prelude::*;
+
+// The definition a node was spawned from
+#[derive(Component, Clone)]
+pub struct Module {
+    pub id: String,
+}
@@ -0,0 +1,12 @@
+// Folder inside the asset folder that holds the module definitions
+pub const ASSET_MODULES_FOLDER: &str = "modules";
+pub const MODULE_EXTENSION: &str = "module.ron";
+// Every file in the modules folder, for the web where the folder can't be listed
+pub const MODULE_FILES: &[&str] = &[
+    "cannon.module.ron",
+    "debris.module.ron",
+    "enemy_cannon.module.ron",
+    "enemy_shield.module.ron",
+    "shield.module.ron",
+    "zapper.module.ron",
+];
@@ -0,0 +1,33 @@
+use super::{constants::MODULE_EXTENSION, ModuleDefinition};
+use bevy::asset::{AssetLoader, AssetPath, BoxedFuture, LoadContext, LoadedAsset};
+
+#[derive(Default)]
+pub struct ModuleDefinitionLoader;
+
+impl AssetLoader for ModuleDefinitionLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let mut definition: ModuleDefinition = ron::de::from_bytes(bytes)?;
+
+            // Load the sprites along with the definition
+            let mut dependencies = vec![AssetPath::from(definition.sprite.as_str()).to_owned()];
+            definition.texture = load_context.get_handle(definition.sprite.as_str());
+            if let Some(forcefield_sprite) = &definition.forcefield_sprite {
+                dependencies.push(AssetPath::from(forcefield_sprite.as_str()).to_owned());
+                definition.forcefield_texture = load_context.get_handle(forcefield_sprite.as_str());
+            }
+
+            load_context
+                .set_default_asset(LoadedAsset::new(definition).with_dependencies(dependencies));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &[MODULE_EXTENSION]
+    }
+}
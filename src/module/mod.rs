@@ -0,0 +1,13 @@
+mod components;
+mod constants;
+mod loader;
+mod plugin;
+mod resources;
+mod systems;
+
+pub use self::{
+    components::Module,
+    constants::ASSET_MODULES_FOLDER,
+    plugin::{ModulePlugin, ModuleSystem},
+    resources::{ModuleDefinition, ModuleDefinitions, ModuleKind},
+};
@@ -0,0 +1,30 @@
+use super::{
+    loader::ModuleDefinitionLoader,
+    resources::ModuleHandles,
+    systems::{load_module_definitions_system, update_module_definitions_system},
+    ModuleDefinition, ModuleDefinitions,
+};
+use crate::{asset::AssetSystem, schedule::GameState};
+use bevy::prelude::*;
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash, SystemLabel)]
+pub struct ModuleSystem;
+
+pub struct ModulePlugin;
+
+impl Plugin for ModulePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<ModuleDefinition>()
+            .init_asset_loader::<ModuleDefinitionLoader>()
+            .init_resource::<ModuleDefinitions>()
+            .init_resource::<ModuleHandles>()
+            .add_system_set(
+                SystemSet::on_enter(GameState::AssetLoading)
+                    .label(ModuleSystem)
+                    .after(AssetSystem)
+                    .with_system(load_module_definitions_system),
+            )
+            // Runs in every state, so edited files are picked up while playing
+            .add_system(update_module_definitions_system.label(ModuleSystem));
+    }
+}
@@ -0,0 +1,82 @@
+use super::constants::MODULE_EXTENSION;
+use crate::{
+    components::{Cannon, Collider, Resistances, ShieldForcefield, Zapper},
+    ron_file::load_ron_folder,
+};
+use bevy::{prelude::*, reflect::TypeUuid, utils::HashMap};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Serialize, Deserialize)]
+pub enum ModuleKind {
+    Debris,
+    Zapper(Zapper),
+    Cannon(Cannon),
+    Shield(ShieldForcefield),
+}
+
+/// A module archetype, loaded from a `.module.ron` file in the `modules` asset folder.
+#[derive(Clone, Serialize, Deserialize, TypeUuid)]
+#[uuid = "5b2e9a4c-6f1d-4c38-9d0e-3a7f1b8c2e61"]
+pub struct ModuleDefinition {
+    pub id: String,
+    pub sprite: String,
+    #[serde(default)]
+    pub forcefield_sprite: Option<String>,
+    // Shape that projectiles and other nodes collide with, the whole sprite by default
+    #[serde(default)]
+    pub collider: Collider,
+    // Hit points of a freshly spawned module
+    pub health: u32,
+    // Damage the module soaks up by type, none by default
+    #[serde(default)]
+    pub resistances: Resistances,
+    // Relative chance of being dropped when the ship it belongs to is destroyed
+    pub drop_weight: f32,
+    // The definition that is dropped instead of this one, if any
+    #[serde(default)]
+    pub salvage: Option<String>,
+    pub kind: ModuleKind,
+
+    // Resolved by the asset loader
+    #[serde(skip)]
+    pub texture: Handle<Image>,
+    #[serde(skip)]
+    pub forcefield_texture: Handle<Image>,
+}
+
+/// All module definitions by id. Kept up to date with the definition assets, so
+/// changes to the files apply to every module spawned afterwards.
+#[derive(Default)]
+pub struct ModuleDefinitions {
+    definitions: HashMap<String, ModuleDefinition>,
+}
+
+impl ModuleDefinitions {
+    /// Reads the definitions straight from disk, for when there is no asset server.
+    pub fn load(path: &str) -> Self {
+        let mut definitions = Self::default();
+        for definition in load_ron_folder(path, MODULE_EXTENSION) {
+            definitions.insert(definition);
+        }
+
+        definitions
+    }
+
+    pub fn insert(&mut self, definition: ModuleDefinition) {
+        self.definitions.insert(definition.id.clone(), definition);
+    }
+
+    /// The definition with the given id, if it is loaded. Saves, blueprints and hot reloaded
+    /// files can all refer to ids that no longer exist.
+    pub fn get(&self, id: &str) -> Option<&ModuleDefinition> {
+        self.definitions.get(id)
+    }
+
+    pub fn remove(&mut self, id: &str) {
+        self.definitions.remove(id);
+    }
+}
+
+// Keeps the handles alive, so the definitions stay loaded and can be hot reloaded
+#[derive(Default)]
+pub struct ModuleHandles(pub Vec<HandleUntyped>);
@@ -0,0 +1,75 @@
+use super::{
+    constants::{ASSET_MODULES_FOLDER, MODULE_FILES},
+    resources::ModuleHandles,
+    ModuleDefinition, ModuleDefinitions,
+};
+use crate::asset::{load_folder, LoadingAssets};
+use bevy::{asset::HandleId, prelude::*, utils::HashMap};
+
+pub fn load_module_definitions_system(
+    asset_server: Res<AssetServer>,
+    mut module_handles: ResMut<ModuleHandles>,
+    mut loading_assets: ResMut<LoadingAssets>,
+) {
+    match load_folder(&asset_server, ASSET_MODULES_FOLDER, MODULE_FILES) {
+        Ok(handles) => {
+            loading_assets.extend(handles.iter().cloned());
+            module_handles.0 = handles;
+        }
+        // The game still runs, just without any modules to spawn
+        Err(err) => error!("Failed to load module definitions: {:?}", err),
+    }
+}
+
+// The ids are remembered by handle, as a removed asset can't be read anymore and an edited one
+// may have been renamed
+pub fn update_module_definitions_system(
+    mut asset_events: EventReader<AssetEvent<ModuleDefinition>>,
+    assets: Res<Assets<ModuleDefinition>>,
+    mut module_definitions: ResMut<ModuleDefinitions>,
+    mut ids: Local<HashMap<HandleId, String>>,
+) {
+    for event in asset_events.iter() {
+        match event {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle } => {
+                if let Some(definition) = assets.get(handle) {
+                    info!("Loaded module definition {}", definition.id);
+                    if let Some(old_id) = ids.insert(handle.id, definition.id.clone()) {
+                        module_definitions.remove(&old_id);
+                    }
+                    module_definitions.insert(definition.clone());
+                }
+            }
+            AssetEvent::Removed { handle } => {
+                if let Some(id) = ids.remove(&handle.id) {
+                    info!("Removed module definition {}", id);
+                    module_definitions.remove(&id);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::module::constants::MODULE_EXTENSION;
+    use std::{fs, path::Path};
+
+    #[test]
+    fn every_module_file_is_listed_for_the_web() {
+        let folder = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("assets")
+            .join(ASSET_MODULES_FOLDER);
+        let mut files: Vec<String> = fs::read_dir(folder)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .filter(|name| name.ends_with(MODULE_EXTENSION))
+            .collect();
+        files.sort();
+        let mut listed = MODULE_FILES.to_vec();
+        listed.sort_unstable();
+
+        assert_eq!(files, listed);
+    }
+}
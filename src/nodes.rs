@@ -1,4 +1,9 @@
-use crate::{components::*, enemy::Enemy};
+use crate::{
+    components::*,
+    enemy::Enemy,
+    module::{Module, ModuleDefinition, ModuleDefinitions, ModuleKind},
+    object::FORCEFIELD_COLLIDER,
+};
 use bevy::prelude::*;
 
 pub fn spawn_empty_node<'a>(
@@ -22,33 +27,133 @@ pub fn spawn_empty_node<'a>(
         .id()
 }
 
-pub fn spawn_shield_node<'a>(
-    commands: &'a mut Commands,
+// Nothing is spawned for a module that isn't defined, or not as the kind that was asked for
+fn find_definition<'a>(
+    definitions: &'a ModuleDefinitions,
+    id: &str,
+) -> Option<&'a ModuleDefinition> {
+    let definition = definitions.get(id);
+    if definition.is_none() {
+        warn!("Unknown module definition {}", id);
+    }
+
+    definition
+}
+
+// Spawns any module, whatever kind its definition is
+pub fn spawn_module_node(
+    commands: &mut Commands,
+    definitions: &ModuleDefinitions,
+    id: &str,
     position: Vec3,
     rotation: f32,
-    asset: Handle<Image>,
-    field_asset: Handle<Image>,
-    shield_stats: ShieldForcefield,
     is_enemy: bool,
+) -> Option<Entity> {
+    match find_definition(definitions, id)?.kind {
+        ModuleKind::Debris => spawn_debris_node(commands, definitions, id, position, rotation),
+        ModuleKind::Zapper(_) => spawn_zapper_node(commands, definitions, id, position, rotation),
+        ModuleKind::Cannon(_) => spawn_cannon_node(commands, definitions, id, position, rotation),
+        ModuleKind::Shield(_) => {
+            spawn_shield_node(commands, definitions, id, position, rotation, is_enemy)
+        }
+    }
+}
+
+fn spawn_definition_node(
+    commands: &mut Commands,
+    definition: &ModuleDefinition,
+    position: Vec3,
+    rotation: f32,
 ) -> Entity {
+    let node = spawn_empty_node(commands, position, rotation, definition.texture.clone());
+
+    commands
+        .entity(node)
+        .insert(Module {
+            id: definition.id.clone(),
+        })
+        .insert(definition.collider)
+        .insert(Health {
+            current: definition.health,
+            max: definition.health,
+        })
+        .insert(definition.resistances.clone())
+        .id()
+}
+
+pub fn spawn_debris_node(
+    commands: &mut Commands,
+    definitions: &ModuleDefinitions,
+    id: &str,
+    position: Vec3,
+    rotation: f32,
+) -> Option<Entity> {
+    let definition = find_definition(definitions, id)?;
+
+    Some(spawn_definition_node(
+        commands, definition, position, rotation,
+    ))
+}
+
+pub fn spawn_shield_node(
+    commands: &mut Commands,
+    definitions: &ModuleDefinitions,
+    id: &str,
+    position: Vec3,
+    rotation: f32,
+    is_enemy: bool,
+) -> Option<Entity> {
+    let stats = match &find_definition(definitions, id)?.kind {
+        ModuleKind::Shield(stats) => stats.clone(),
+        _ => {
+            warn!("Module {} is not a shield", id);
+            return None;
+        }
+    };
+
+    spawn_shield_node_with_stats(
+        commands,
+        definitions,
+        id,
+        position,
+        rotation,
+        stats,
+        is_enemy,
+    )
+}
+
+// Used to restore a shield whose forcefield has taken damage
+pub fn spawn_shield_node_with_stats(
+    commands: &mut Commands,
+    definitions: &ModuleDefinitions,
+    id: &str,
+    position: Vec3,
+    rotation: f32,
+    shield_stats: ShieldForcefield,
+    is_enemy: bool,
+) -> Option<Entity> {
+    let definition = find_definition(definitions, id)?;
+
     // Spawn the forcefield and add it as a child to the shield
     let forcefield = spawn_shield_forcefield(
         commands,
         Vec3::new(0., 0., 0.),
         std::f32::consts::PI / 4.,
         Vec3::new(1.5, 1.5, 1.),
-        field_asset,
+        definition.forcefield_texture.clone(),
         shield_stats,
     );
     if is_enemy {
         commands.entity(forcefield).insert(Enemy {});
     }
-    let shield_node = spawn_empty_node(commands, position, rotation, asset);
-    commands
-        .entity(shield_node)
-        .insert(Shield {})
-        .add_child(forcefield)
-        .id()
+    let shield_node = spawn_definition_node(commands, definition, position, rotation);
+    Some(
+        commands
+            .entity(shield_node)
+            .insert(Shield {})
+            .add_child(forcefield)
+            .id(),
+    )
 }
 
 pub fn spawn_shield_forcefield<'a>(
@@ -68,33 +173,57 @@ pub fn spawn_shield_forcefield<'a>(
                 rotation: Quat::from_rotation_z(rotation),
             },
             texture: asset,
+            // A shield that is still recharging has its forcefield hidden
+            visibility: Visibility {
+                is_visible: stats.is_up(),
+            },
             ..default()
         })
         .id();
 
-    commands.entity(forcefield_node).insert(stats).id()
+    commands
+        .entity(forcefield_node)
+        .insert(FORCEFIELD_COLLIDER)
+        .insert(stats)
+        .id()
 }
 
-pub fn spawn_zapper_node<'a>(
-    commands: &'a mut Commands,
+pub fn spawn_zapper_node(
+    commands: &mut Commands,
+    definitions: &ModuleDefinitions,
+    id: &str,
     position: Vec3,
     rotation: f32,
-    asset: Handle<Image>,
-    stats: Zapper,
-) -> Entity {
-    let turret = spawn_empty_node(commands, position, rotation, asset);
+) -> Option<Entity> {
+    let definition = find_definition(definitions, id)?;
+    let stats = match &definition.kind {
+        ModuleKind::Zapper(stats) => stats.clone(),
+        _ => {
+            warn!("Module {} is not a zapper", id);
+            return None;
+        }
+    };
+    let turret = spawn_definition_node(commands, definition, position, rotation);
 
-    commands.entity(turret).insert(stats).id()
+    Some(commands.entity(turret).insert(stats).id())
 }
 
 pub fn spawn_cannon_node(
     commands: &mut Commands,
+    definitions: &ModuleDefinitions,
+    id: &str,
     position: Vec3,
     rotation: f32,
-    asset: Handle<Image>,
-    stats: Cannon,
-) -> Entity {
-    let turret = spawn_empty_node(commands, position, rotation, asset);
+) -> Option<Entity> {
+    let definition = find_definition(definitions, id)?;
+    let stats = match &definition.kind {
+        ModuleKind::Cannon(stats) => stats.clone(),
+        _ => {
+            warn!("Module {} is not a cannon", id);
+            return None;
+        }
+    };
+    let turret = spawn_definition_node(commands, definition, position, rotation);
 
-    commands.entity(turret).insert(stats).id()
+    Some(commands.entity(turret).insert(stats).id())
 }
@@ -0,0 +1,11 @@
+use crate::components::Collider;
+
+// The bullets are scaled up 2x2 sprites
+pub const BULLET_COLLIDER: Collider = Collider::Circle { radius: 0.5 };
+// Half of the 24x24 forcefield sprite
+pub const FORCEFIELD_COLLIDER: Collider = Collider::Circle { radius: 12. };
+// Opacity of a forcefield that is about to break, a full one is opaque
+pub const FORCEFIELD_MIN_ALPHA: f32 = 0.2;
+
+// Units per second
+pub const PROJECTILE_SPEED: f32 = 120.;
@@ -1,9 +1,11 @@
 mod components;
+mod constants;
 mod plugin;
 pub(crate) mod systems;
 
 pub use self::{
     components::*,
+    constants::{BULLET_COLLIDER, FORCEFIELD_COLLIDER, PROJECTILE_SPEED},
     plugin::{ObjectPlugin, ObjectSystem},
     systems::move_objects_system,
 };
@@ -1,15 +1,18 @@
 use super::{
     systems::{
-        bullet_collision, clean_bullets, forcefield_cooldown_system, move_objects_system,
-        move_projectile, spawn_start_objects_system, velocity_dropoff_system,
+        clean_bullets, forcefield_cooldown_system, move_objects_system, move_projectile,
+        move_ships_system, spawn_start_objects_system, velocity_dropoff_system,
     },
     Object,
 };
 use crate::{
     components::{Projectile, ZapEffect},
     despawn_recursive::despawn_entities_recursive_system,
+    physics::{on_physics_update, PhysicsStage, PhysicsSystem},
     player::PlayerSystem,
+    random::RandomSystem,
     schedule::GameState,
+    weapon::WeaponSystem,
 };
 use bevy::prelude::*;
 
@@ -23,18 +26,26 @@ impl Plugin for ObjectPlugin {
         app.add_system_set(
             SystemSet::on_enter(GameState::BeforeInGame)
                 .label(ObjectSystem)
+                .after(RandomSystem)
                 .with_system(spawn_start_objects_system),
         )
-        .add_system_set(
-            SystemSet::on_update(GameState::InGame)
+        .add_system_set_to_stage(
+            PhysicsStage,
+            on_physics_update(GameState::InGame)
                 .label(ObjectSystem)
+                .after(PhysicsSystem::Propagate)
                 .before(PlayerSystem)
+                .with_system(forcefield_cooldown_system)
+                .with_system(clean_bullets.after(WeaponSystem)),
+        )
+        .add_system_set_to_stage(
+            PhysicsStage,
+            on_physics_update(GameState::InGame)
+                .label(PhysicsSystem::Movement)
                 .with_system(move_objects_system)
                 .with_system(move_projectile)
-                .with_system(velocity_dropoff_system)
-                .with_system(bullet_collision)
-                .with_system(forcefield_cooldown_system)
-                .with_system(clean_bullets.after(bullet_collision)),
+                .with_system(move_ships_system)
+                .with_system(velocity_dropoff_system.after(move_objects_system)),
         )
         .add_system_set(
             SystemSet::on_exit(GameState::AfterInGame)
@@ -1,16 +1,15 @@
-use super::Object;
+use super::{constants::FORCEFIELD_MIN_ALPHA, Object};
 use crate::{
-    asset::SpriteHandles,
-    audio::{AudioEvent, AudioType},
-    components::{
-        Bullet, Cannon, Collider, Projectile, Properties, ShieldForcefield, Velocity, Zapper,
-    },
-    enemy::Enemy,
-    events::Hit,
-    nodes::{spawn_cannon_node, spawn_empty_node, spawn_zapper_node},
+    components::{Collider, Projectile, Properties, ShieldForcefield, Velocity},
+    enemy::{Enemy, EnemyRoot},
+    module::ModuleDefinitions,
+    nodes::{spawn_cannon_node, spawn_debris_node, spawn_zapper_node},
+    physics::{OBJECT_DRAG, PHYSICS_TIMESTEP, SHIP_DRAG},
     player::{Player, PlayerRoot},
+    random::GameRng,
 };
 use bevy::prelude::*;
+use rand::Rng;
 use std::f32::consts::TAU;
 
 #[allow(clippy::type_complexity)]
@@ -21,16 +20,30 @@ pub fn move_objects_system(
     >,
 ) {
     for (mut transform, velocity) in query.iter_mut() {
-        transform.translation.x += 1. * velocity.x as f32;
-        transform.translation.y += 1. * velocity.y as f32;
+        transform.translation.x += velocity.x * PHYSICS_TIMESTEP;
+        transform.translation.y += velocity.y * PHYSICS_TIMESTEP;
         let (_, _, z) = transform.rotation.to_euler(EulerRot::XYZ);
-        transform.rotation = Quat::from_rotation_z(z + velocity.rotation);
+        transform.rotation = Quat::from_rotation_z(z + velocity.rotation * PHYSICS_TIMESTEP);
+    }
+}
+
+// Ships only get velocity from knockback, their own movement is steered separately
+pub fn move_ships_system(
+    mut query: Query<(&mut Transform, &mut Velocity), Or<(With<PlayerRoot>, With<EnemyRoot>)>>,
+) {
+    let drag = SHIP_DRAG.powf(PHYSICS_TIMESTEP);
+    for (mut transform, mut velocity) in query.iter_mut() {
+        transform.translation.x += velocity.x * PHYSICS_TIMESTEP;
+        transform.translation.y += velocity.y * PHYSICS_TIMESTEP;
+        velocity.x *= drag;
+        velocity.y *= drag;
     }
 }
 
 pub fn _spawn_object_system(
     mut commands: Commands,
     windows: Res<Windows>,
+    mut rng: ResMut<GameRng>,
     player_query: Query<&Transform, With<PlayerRoot>>,
 ) {
     let player_transform = player_query.single();
@@ -38,13 +51,13 @@ pub fn _spawn_object_system(
     let height = windows.primary().height() as f32;
 
     // Random position on the screen
-    let x = player_transform.translation.x + rand::random::<f32>() * width - width / 2.;
-    let y = player_transform.translation.y + rand::random::<f32>() * height - height / 2.;
+    let x = player_transform.translation.x + rng.gen::<f32>() * width - width / 2.;
+    let y = player_transform.translation.y + rng.gen::<f32>() * height - height / 2.;
     let position = Vec3::new(x, y, 0.);
 
     commands
         .spawn()
-        .insert(Collider)
+        .insert(Collider::default())
         .insert(Object)
         .insert(Enemy)
         .insert_bundle(SpriteBundle {
@@ -60,9 +73,9 @@ pub fn _spawn_object_system(
             ..default()
         })
         .insert(Velocity {
-            x: rand::random::<f32>() * 2. - 1.,
-            y: rand::random::<f32>() * 2. - 1.,
-            rotation: rand::random::<f32>() * 0.2 - 0.1,
+            x: rng.gen::<f32>() * 120. - 60.,
+            y: rng.gen::<f32>() * 120. - 60.,
+            rotation: rng.gen::<f32>() * 12. - 6.,
         })
         .insert(Properties { size: 1, health: 1 });
 }
@@ -88,171 +101,97 @@ pub fn _clean_objects_system(
     }
 }
 
-pub fn spawn_start_objects_system(mut commands: Commands, sprite_handles: Res<SpriteHandles>) {
+pub fn spawn_start_objects_system(
+    mut commands: Commands,
+    module_definitions: Res<ModuleDefinitions>,
+    mut rng: ResMut<GameRng>,
+) {
     // Place two zappers
     let starter_zapper = spawn_zapper_node(
         &mut commands,
+        &module_definitions,
+        "zapper",
         Vec3::new(
-            rand::random::<f32>() * 200. - 100.,
-            rand::random::<f32>() * 200. - 100.,
+            rng.gen::<f32>() * 200. - 100.,
+            rng.gen::<f32>() * 200. - 100.,
             0.,
         ),
         0.,
-        sprite_handles.zapper.clone(),
-        Zapper {
-            damage: 10,
-            fire_rate: 1.,
-            cooldown_timer: 0.,
-            range: 100.,
-        },
     );
 
-    commands.entity(starter_zapper).insert(Object);
+    if let Some(starter_zapper) = starter_zapper {
+        commands.entity(starter_zapper).insert(Object);
+    }
 
     let starter_zapper = spawn_zapper_node(
         &mut commands,
+        &module_definitions,
+        "zapper",
         Vec3::new(
-            rand::random::<f32>() * 200. - 100.,
-            rand::random::<f32>() * 200. - 100.,
+            rng.gen::<f32>() * 200. - 100.,
+            rng.gen::<f32>() * 200. - 100.,
             0.,
         ),
         0.,
-        sprite_handles.zapper.clone(),
-        Zapper {
-            damage: 10,
-            fire_rate: 1.,
-            cooldown_timer: 0.,
-            range: 100.,
-        },
     );
 
-    commands.entity(starter_zapper).insert(Object);
+    if let Some(starter_zapper) = starter_zapper {
+        commands.entity(starter_zapper).insert(Object);
+    }
 
     let starter_cannon = spawn_cannon_node(
         &mut commands,
+        &module_definitions,
+        "cannon",
         Vec3::new(
-            rand::random::<f32>() * 200. - 100.,
-            rand::random::<f32>() * 200. - 100.,
+            rng.gen::<f32>() * 200. - 100.,
+            rng.gen::<f32>() * 200. - 100.,
             0.,
         ),
         0.,
-        sprite_handles.cannon.clone(),
-        Cannon {
-            damage: 10,
-            fire_rate: 1.,
-            cooldown_timer: 0.,
-            range: 100.,
-        },
     );
-    commands.entity(starter_cannon).insert(Object);
+    if let Some(starter_cannon) = starter_cannon {
+        commands.entity(starter_cannon).insert(Object);
+    }
 
     // Some uniformly distributed debris around the player
     for _ in 0..5 {
         // Spawn a node with debris
-        let debris = spawn_empty_node(
+        let debris = spawn_debris_node(
             &mut commands,
+            &module_definitions,
+            "debris",
             Vec3::new(
-                rand::random::<f32>() * 200. - 100.,
-                rand::random::<f32>() * 200. - 100.,
+                rng.gen::<f32>() * 200. - 100.,
+                rng.gen::<f32>() * 200. - 100.,
                 0.,
             ),
-            rand::random::<f32>() * TAU,
-            sprite_handles.debris.clone(),
+            rng.gen::<f32>() * TAU,
         );
 
-        commands.entity(debris).insert(Object).insert(Velocity {
-            x: 0.,
-            y: 0.,
-            rotation: rand::random::<f32>() * 0.2,
-        });
+        if let Some(debris) = debris {
+            commands.entity(debris).insert(Object).insert(Velocity {
+                x: 0.,
+                y: 0.,
+                rotation: rng.gen::<f32>() * 12.,
+            });
+        }
     }
 }
 
 pub fn velocity_dropoff_system(mut query: Query<&mut Velocity, With<Object>>) {
+    let drag = OBJECT_DRAG.powf(PHYSICS_TIMESTEP);
     for mut velocity in query.iter_mut() {
-        velocity.x *= 0.99;
-        velocity.y *= 0.99;
-        velocity.rotation *= 0.99;
+        velocity.x *= drag;
+        velocity.y *= drag;
+        velocity.rotation *= drag;
     }
 }
 
 pub fn move_projectile(mut query: Query<(&mut Transform, &Velocity), With<Projectile>>) {
     for (mut transform, velocity) in query.iter_mut() {
-        transform.translation.x += 1. * velocity.x as f32;
-        transform.translation.y += 1. * velocity.y as f32;
-    }
-}
-
-#[allow(clippy::type_complexity)]
-pub fn bullet_collision(
-    mut commands: Commands,
-    mut event_hit: EventWriter<Hit>,
-    mut event_audio: EventWriter<AudioEvent>,
-    hittable_query: Query<
-        (Entity, &GlobalTransform, Option<&Enemy>, &Parent),
-        Or<(With<Enemy>, With<Player>)>,
-    >,
-    bullet_query: Query<(Entity, &Transform, &Bullet)>,
-    mut forcefield_query: Query<
-        (
-            &GlobalTransform,
-            &mut Visibility,
-            &mut ShieldForcefield,
-            Option<&Enemy>,
-        ),
-        With<Parent>,
-    >,
-) {
-    // Check for forcefield collision
-    // On forcefield collision, just do the forcefield damage here and remove the bullet
-    // Forcefield radius is 18 units
-    for (forcefield_transform, mut forcefield_visibility, mut forcefield_stats, is_enemy) in
-        forcefield_query.iter_mut()
-    {
-        for (bullet_entity, bullet_transform, bullet_stats) in bullet_query.iter() {
-            let distance = forcefield_transform
-                .compute_transform()
-                .translation
-                .distance(bullet_transform.translation);
-            if distance < 18. && forcefield_visibility.is_visible {
-                // If the bullet is by an enemy and the currently viewed forcefield is by an enemy, ignore it, as that would be a self hit
-                if bullet_stats.enemy == is_enemy.is_some() {
-                    continue;
-                }
-                forcefield_stats.health =
-                    forcefield_stats.health.saturating_sub(bullet_stats.damage);
-                if forcefield_stats.health == 0 {
-                    forcefield_visibility.is_visible = false;
-                    forcefield_stats.cooldown_timer = forcefield_stats.cooldown;
-                }
-
-                commands.entity(bullet_entity).despawn();
-                return;
-            }
-        }
-    }
-
-    // If no forcefield, check for enemy collision
-    // On collision, do hit event and remove bullet
-    for (_hittable_entity, hittable_transform, is_enemy, parent) in hittable_query.iter() {
-        for (bullet_entity, bullet_transform, bullet_stats) in bullet_query.iter() {
-            let distance = hittable_transform
-                .compute_transform()
-                .translation
-                .distance(bullet_transform.translation);
-            if distance < 5. {
-                if bullet_stats.enemy == is_enemy.is_some() {
-                    continue;
-                }
-                event_hit.send(Hit {
-                    target: parent.get(),
-                    damage: bullet_stats.damage,
-                });
-                event_audio.send(AudioEvent(AudioType::Hit));
-                commands.entity(bullet_entity).despawn();
-                return;
-            }
-        }
+        transform.translation.x += velocity.x * PHYSICS_TIMESTEP;
+        transform.translation.y += velocity.y * PHYSICS_TIMESTEP;
     }
 }
 
@@ -275,15 +214,20 @@ pub fn clean_bullets(
 }
 
 pub fn forcefield_cooldown_system(
-    mut forcefield_query: Query<(&mut ShieldForcefield, &mut Visibility), With<Parent>>,
-    time: Res<Time>,
+    mut forcefield_query: Query<
+        (&mut ShieldForcefield, &mut Visibility, &mut Sprite),
+        With<Parent>,
+    >,
 ) {
-    for (mut forcefield_stats, mut forcefield_visibility) in forcefield_query.iter_mut() {
-        if forcefield_stats.cooldown_timer > 0. {
-            forcefield_stats.cooldown_timer -= time.delta_seconds();
-        }
-        if forcefield_stats.cooldown_timer <= 0. {
-            forcefield_visibility.is_visible = true;
-        }
+    for (mut forcefield_stats, mut forcefield_visibility, mut forcefield_sprite) in
+        forcefield_query.iter_mut()
+    {
+        forcefield_stats.recharge(PHYSICS_TIMESTEP);
+
+        forcefield_visibility.is_visible = forcefield_stats.is_up();
+        // The weaker the forcefield, the fainter it is
+        forcefield_sprite.color.set_a(
+            FORCEFIELD_MIN_ALPHA + (1. - FORCEFIELD_MIN_ALPHA) * forcefield_stats.strength(),
+        );
     }
 }
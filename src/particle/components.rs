@@ -0,0 +1,27 @@
+use bevy::prelude::*;
+
+/// What the particles of a burst look like and how they move.
+#[derive(Clone, Copy)]
+pub struct ParticleEmitter {
+    pub count: u32,
+    // Seconds a particle lives
+    pub lifetime: f32,
+    // The speed of a particle is picked between these
+    pub min_speed: f32,
+    pub max_speed: f32,
+    // Largest angle between a particle and the direction of the burst, PI for all around
+    pub spread: f32,
+    // The color and size of a particle go from start to end over its lifetime
+    pub start_color: Color,
+    pub end_color: Color,
+    pub start_size: f32,
+    pub end_size: f32,
+}
+
+// Particles are never despawned while playing, dead ones are hidden and reused
+#[derive(Component)]
+pub struct Particle {
+    pub velocity: Vec2,
+    pub age: f32,
+    pub emitter: ParticleEmitter,
+}
@@ -0,0 +1,75 @@
+use super::ParticleEmitter;
+use crate::colors::{COLOR_ACCENT, COLOR_FOREGROUND, COLOR_PLAYER, COLOR_TRANSPARENT};
+use bevy::prelude::Color;
+use std::f32::consts::PI;
+
+// Beyond this, bursts are cut short so big battles stay fast
+pub const MAX_PARTICLES: usize = 1000;
+
+// Above the enemies and loose objects, but still in front of the camera at a height of 1
+pub const PARTICLE_Z: f32 = 0.9;
+
+// A ship that was destroyed
+pub const EXPLOSION_EMITTER: ParticleEmitter = ParticleEmitter {
+    count: 40,
+    lifetime: 0.8,
+    min_speed: 20.,
+    max_speed: 90.,
+    spread: PI,
+    start_color: Color::rgb(1., 0.9, 0.5),
+    end_color: COLOR_TRANSPARENT,
+    start_size: 3.,
+    end_size: 1.,
+};
+
+// The player's ship going up, bigger than any other explosion
+pub const DEATH_EMITTER: ParticleEmitter = ParticleEmitter {
+    count: 120,
+    lifetime: 1.5,
+    min_speed: 30.,
+    max_speed: 160.,
+    spread: PI,
+    start_color: COLOR_ACCENT,
+    end_color: COLOR_TRANSPARENT,
+    start_size: 4.,
+    end_size: 1.,
+};
+
+// Sparks flying off where a node got hit
+pub const HIT_EMITTER: ParticleEmitter = ParticleEmitter {
+    count: 6,
+    lifetime: 0.3,
+    min_speed: 30.,
+    max_speed: 70.,
+    spread: PI / 4.,
+    start_color: COLOR_FOREGROUND,
+    end_color: COLOR_TRANSPARENT,
+    start_size: 2.,
+    end_size: 1.,
+};
+
+// A module snapping onto the player's ship
+pub const ATTACHMENT_EMITTER: ParticleEmitter = ParticleEmitter {
+    count: 12,
+    lifetime: 0.4,
+    min_speed: 10.,
+    max_speed: 30.,
+    spread: PI,
+    start_color: COLOR_PLAYER,
+    end_color: COLOR_TRANSPARENT,
+    start_size: 2.,
+    end_size: 2.,
+};
+
+// Left behind the player's ship while it moves, once per physics step
+pub const ENGINE_EMITTER: ParticleEmitter = ParticleEmitter {
+    count: 1,
+    lifetime: 0.5,
+    min_speed: 10.,
+    max_speed: 20.,
+    spread: PI / 8.,
+    start_color: COLOR_PLAYER,
+    end_color: COLOR_TRANSPARENT,
+    start_size: 2.,
+    end_size: 0.5,
+};
@@ -0,0 +1,9 @@
+use super::ParticleEmitter;
+use bevy::prelude::*;
+
+pub struct ParticleBurst {
+    pub position: Vec2,
+    // The center of the spread of the emitter
+    pub direction: Vec2,
+    pub emitter: ParticleEmitter,
+}
@@ -0,0 +1,12 @@
+mod components;
+mod constants;
+mod events;
+mod plugin;
+mod resources;
+mod systems;
+
+pub use self::{
+    components::{Particle, ParticleEmitter},
+    events::ParticleBurst,
+    plugin::{ParticlePlugin, ParticleSystem},
+};
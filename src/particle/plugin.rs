@@ -0,0 +1,60 @@
+use super::{
+    resources::{ParticlePool, ParticleRng},
+    systems::{
+        burst_on_attachment_system, burst_on_enemy_killed_system, burst_on_hit_system,
+        burst_on_player_death_system, clear_particles_system, emit_particles_system,
+        engine_trail_system, update_particles_system,
+    },
+    ParticleBurst,
+};
+use crate::{
+    physics::{on_physics_update, PhysicsStage},
+    schedule::{in_game_or_after, GameState},
+};
+use bevy::prelude::*;
+use rand::{rngs::StdRng, SeedableRng};
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash, SystemLabel)]
+pub struct ParticleSystem;
+
+// Explosions, sparks and engine trails, made of pooled sprites
+pub struct ParticlePlugin;
+
+impl Plugin for ParticlePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ParticleBurst>()
+            .init_resource::<ParticlePool>()
+            .insert_resource(ParticleRng(StdRng::from_entropy()))
+            .add_system_set(
+                SystemSet::on_update(GameState::InGame)
+                    .label(ParticleSystem)
+                    .with_system(burst_on_enemy_killed_system)
+                    .with_system(burst_on_hit_system)
+                    .with_system(burst_on_attachment_system),
+            )
+            .add_system_set(
+                SystemSet::on_enter(GameState::AfterInGame)
+                    .label(ParticleSystem)
+                    .with_system(burst_on_player_death_system),
+            )
+            // Particles keep going while the death explosion plays out
+            .add_system_set(
+                SystemSet::new()
+                    .with_run_criteria(in_game_or_after)
+                    .label(ParticleSystem)
+                    .with_system(emit_particles_system)
+                    .with_system(update_particles_system.after(emit_particles_system)),
+            )
+            .add_system_set(
+                SystemSet::on_exit(GameState::AfterInGame)
+                    .label(ParticleSystem)
+                    .with_system(clear_particles_system),
+            )
+            .add_system_set_to_stage(
+                PhysicsStage,
+                on_physics_update(GameState::InGame)
+                    .label(ParticleSystem)
+                    .with_system(engine_trail_system),
+            );
+    }
+}
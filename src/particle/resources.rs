@@ -0,0 +1,12 @@
+use bevy::prelude::*;
+use rand::rngs::StdRng;
+
+/// The particle entities that can be reused, and how many are in use.
+#[derive(Default)]
+pub struct ParticlePool {
+    pub free: Vec<Entity>,
+    pub live: usize,
+}
+
+// Particles are only visual, so they don't draw from the `GameRng` and runs stay reproducible
+pub struct ParticleRng(pub StdRng);
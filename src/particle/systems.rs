@@ -0,0 +1,198 @@
+use super::{
+    constants::{
+        ATTACHMENT_EMITTER, DEATH_EMITTER, ENGINE_EMITTER, EXPLOSION_EMITTER, HIT_EMITTER,
+        MAX_PARTICLES, PARTICLE_Z,
+    },
+    resources::{ParticlePool, ParticleRng},
+    Particle, ParticleBurst,
+};
+use crate::{
+    enemy::EnemyKilled,
+    events::Hit,
+    player::{PlayerInput, PlayerRoot, PlayerSizeIncreased},
+};
+use bevy::prelude::*;
+use rand::Rng;
+
+pub fn burst_on_enemy_killed_system(
+    mut enemy_killed_events: EventReader<EnemyKilled>,
+    mut particle_bursts: EventWriter<ParticleBurst>,
+) {
+    for position in enemy_killed_events
+        .iter()
+        .filter_map(|event| event.position)
+    {
+        particle_bursts.send(ParticleBurst {
+            position,
+            direction: Vec2::X,
+            emitter: EXPLOSION_EMITTER,
+        });
+    }
+}
+
+pub fn burst_on_hit_system(
+    mut hit_events: EventReader<Hit>,
+    mut particle_bursts: EventWriter<ParticleBurst>,
+) {
+    for hit in hit_events.iter() {
+        // The sparks fly back towards the shooter
+        particle_bursts.send(ParticleBurst {
+            position: hit.position,
+            direction: -hit.impulse,
+            emitter: HIT_EMITTER,
+        });
+    }
+}
+
+pub fn burst_on_attachment_system(
+    mut player_size_increased_events: EventReader<PlayerSizeIncreased>,
+    mut particle_bursts: EventWriter<ParticleBurst>,
+) {
+    for event in player_size_increased_events.iter() {
+        particle_bursts.send(ParticleBurst {
+            position: event.position,
+            direction: Vec2::X,
+            emitter: ATTACHMENT_EMITTER,
+        });
+    }
+}
+
+pub fn burst_on_player_death_system(
+    player_query: Query<&Transform, With<PlayerRoot>>,
+    mut particle_bursts: EventWriter<ParticleBurst>,
+) {
+    for transform in player_query.iter() {
+        particle_bursts.send(ParticleBurst {
+            position: transform.translation.truncate(),
+            direction: Vec2::X,
+            emitter: DEATH_EMITTER,
+        });
+    }
+}
+
+pub fn engine_trail_system(
+    player_input: Res<PlayerInput>,
+    player_query: Query<&Transform, With<PlayerRoot>>,
+    mut particle_bursts: EventWriter<ParticleBurst>,
+) {
+    if player_input.movement == Vec2::ZERO {
+        return;
+    }
+
+    for transform in player_query.iter() {
+        particle_bursts.send(ParticleBurst {
+            position: transform.translation.truncate(),
+            direction: -player_input.movement,
+            emitter: ENGINE_EMITTER,
+        });
+    }
+}
+
+pub fn emit_particles_system(
+    mut commands: Commands,
+    mut rng: ResMut<ParticleRng>,
+    mut pool: ResMut<ParticlePool>,
+    mut particle_bursts: EventReader<ParticleBurst>,
+    mut particle_query: Query<(&mut Particle, &mut Transform, &mut Sprite, &mut Visibility)>,
+) {
+    for burst in particle_bursts.iter() {
+        let base_angle = burst.direction.y.atan2(burst.direction.x);
+
+        for _ in 0..burst.emitter.count {
+            if pool.live >= MAX_PARTICLES {
+                return;
+            }
+
+            let angle = base_angle + rng.0.gen_range(-1_f32..=1.) * burst.emitter.spread;
+            let speed = rng
+                .0
+                .gen_range(burst.emitter.min_speed..=burst.emitter.max_speed);
+            let particle = Particle {
+                velocity: Vec2::new(angle.cos(), angle.sin()) * speed,
+                age: 0.,
+                emitter: burst.emitter,
+            };
+            let translation = burst.position.extend(PARTICLE_Z);
+            let sprite = Sprite {
+                color: burst.emitter.start_color,
+                custom_size: Some(Vec2::splat(burst.emitter.start_size)),
+                ..default()
+            };
+            pool.live += 1;
+
+            // Reuse a dead particle if there is one
+            if let Some(entity) = pool.free.pop() {
+                if let Ok((mut old_particle, mut transform, mut old_sprite, mut visibility)) =
+                    particle_query.get_mut(entity)
+                {
+                    *old_particle = particle;
+                    transform.translation = translation;
+                    *old_sprite = sprite;
+                    visibility.is_visible = true;
+                    continue;
+                }
+            }
+
+            commands
+                .spawn()
+                .insert_bundle(SpriteBundle {
+                    transform: Transform::from_translation(translation),
+                    sprite,
+                    ..default()
+                })
+                .insert(particle);
+        }
+    }
+}
+
+pub fn update_particles_system(
+    time: Res<Time>,
+    mut pool: ResMut<ParticlePool>,
+    mut particle_query: Query<(
+        Entity,
+        &mut Particle,
+        &mut Transform,
+        &mut Sprite,
+        &mut Visibility,
+    )>,
+) {
+    for (entity, mut particle, mut transform, mut sprite, mut visibility) in
+        particle_query.iter_mut()
+    {
+        if !visibility.is_visible {
+            continue;
+        }
+
+        particle.age += time.delta_seconds();
+        if particle.age >= particle.emitter.lifetime {
+            visibility.is_visible = false;
+            pool.free.push(entity);
+            pool.live -= 1;
+            continue;
+        }
+
+        let movement = particle.velocity * time.delta_seconds();
+        transform.translation += movement.extend(0.);
+
+        // Ramp color and size over the lifetime
+        let t = particle.age / particle.emitter.lifetime;
+        let start = Vec4::from(particle.emitter.start_color.as_rgba_f32());
+        let end = Vec4::from(particle.emitter.end_color.as_rgba_f32());
+        sprite.color = start.lerp(end, t).into();
+        let size = particle.emitter.start_size
+            + (particle.emitter.end_size - particle.emitter.start_size) * t;
+        sprite.custom_size = Some(Vec2::splat(size));
+    }
+}
+
+// The pool is filled again in the next run
+pub fn clear_particles_system(
+    mut commands: Commands,
+    mut pool: ResMut<ParticlePool>,
+    particle_query: Query<Entity, With<Particle>>,
+) {
+    for entity in particle_query.iter() {
+        commands.entity(entity).despawn();
+    }
+    *pool = ParticlePool::default();
+}
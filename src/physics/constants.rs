@@ -0,0 +1,8 @@
+// Length of one simulation step in seconds
+pub const PHYSICS_TIMESTEP: f32 = 1. / 60.;
+
+// Share of their velocity that loose objects keep after one second
+pub const OBJECT_DRAG: f32 = 0.547;
+
+// Ships steer against knockback, so they lose it much faster than loose objects
+pub const SHIP_DRAG: f32 = 0.02;
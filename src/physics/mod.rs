@@ -0,0 +1,7 @@
+mod constants;
+mod plugin;
+
+pub use self::{
+    constants::*,
+    plugin::{on_physics_update, PhysicsPlugin, PhysicsStage, PhysicsSystem},
+};
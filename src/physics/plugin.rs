@@ -0,0 +1,51 @@
+use super::PHYSICS_TIMESTEP;
+use crate::schedule::GameState;
+use bevy::{
+    ecs::schedule::ShouldRun, prelude::*, time::FixedTimestep,
+    transform::transform_propagate_system,
+};
+
+/// Runs all motion in fixed steps, so it doesn't depend on the frame rate.
+/// Systems in this stage use `PHYSICS_TIMESTEP` instead of the frame's delta time.
+#[derive(Debug, PartialEq, Eq, Clone, Hash, StageLabel)]
+pub struct PhysicsStage;
+
+/// The phases of a step in the physics stage. Everything moves first, then the gameplay
+/// reacts to where it ended up, so a step plays out the same at any frame rate.
+#[derive(Debug, PartialEq, Eq, Clone, Hash, SystemLabel)]
+pub enum PhysicsSystem {
+    Movement,
+    // The global transforms are otherwise only updated once per frame, after all steps
+    Propagate,
+}
+
+/// Like `SystemSet::on_update`, but for the physics stage. That stage has no state driver,
+/// as it would otherwise handle the state transitions that are meant for the update stage.
+pub fn on_physics_update(state: GameState) -> SystemSet {
+    SystemSet::new().with_run_criteria(move |game_state: Res<State<GameState>>| {
+        if *game_state.current() == state {
+            ShouldRun::Yes
+        } else {
+            ShouldRun::No
+        }
+    })
+}
+
+pub struct PhysicsPlugin;
+
+impl Plugin for PhysicsPlugin {
+    fn build(&self, app: &mut App) {
+        // Runs before the update, so everything reacts to the new positions in the same frame
+        app.add_stage_before(
+            CoreStage::Update,
+            PhysicsStage,
+            SystemStage::parallel().with_run_criteria(FixedTimestep::step(PHYSICS_TIMESTEP as f64)),
+        )
+        .add_system_to_stage(
+            PhysicsStage,
+            transform_propagate_system
+                .label(PhysicsSystem::Propagate)
+                .after(PhysicsSystem::Movement),
+        );
+    }
+}
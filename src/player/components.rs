@@ -3,6 +3,10 @@ use bevy::prelude::*;
 #[derive(Component)]
 pub struct Player;
 
+// The node the ship started out with, which sits on top of the root
+#[derive(Component)]
+pub struct PlayerCore;
+
 #[derive(Component)]
 pub struct PlayerRoot {
     pub dist: f32,
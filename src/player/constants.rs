@@ -1,2 +1,12 @@
 // Movement
 pub const PLAYER_SPEED: f32 = 75.;
+
+// The run is over when the core runs out of health
+pub const PLAYER_CORE_HEALTH: u32 = 100;
+
+// Attachment
+// How far apart the colliders of an object and a node can be and still attach
+pub const ATTACHMENT_MARGIN: f32 = 1.;
+
+// Units per second with which pieces that broke off drift away from the ship
+pub const SEVERED_NODE_SPEED: f32 = 30.;
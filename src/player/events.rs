@@ -1 +1,6 @@
-pub struct PlayerSizeIncreased;
+use bevy::prelude::*;
+
+pub struct PlayerSizeIncreased {
+    // Where the new node was attached, in world space
+    pub position: Vec2,
+}
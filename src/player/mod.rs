@@ -8,6 +8,7 @@ mod systems;
 pub use self::{
     components::*,
     events::PlayerSizeIncreased,
-    plugin::{PlayerPlugin, PlayerSystem},
+    plugin::{PlayerInputSystem, PlayerPlugin, PlayerSystem},
     resources::*,
+    systems::{explode_player_system, move_player_system, rotate_player_system},
 };
@@ -1,19 +1,17 @@
-use std::time::Duration;
-
 use super::{
     systems::{
         check_attachment_system, check_hits_system, check_player_death_system,
-        explode_player_system, move_player_system, remove_zap_effect_system,
-        reset_sprite_tint_system, rotate_player_system, shoot_player_cannon_system,
-        shoot_player_zapper_system, spawn_player_system, update_player_history_system,
-        update_player_properties_system,
+        detach_severed_nodes_system, explode_player_system, move_player_system,
+        read_player_input_system, remove_zap_effect_system, reset_sprite_tint_system,
+        rotate_player_system, spawn_player_system, update_player_properties_system,
+        update_ship_health_system,
     },
-    PlayerHistory, PlayerRoot, PlayerSizeIncreased,
+    CursorWorldPosition, PlayerInput, PlayerRoot, PlayerSizeIncreased,
 };
 use crate::{
     despawn_recursive::despawn_entities_recursive_system,
-    enemy::systems::shoot_zappy_enemy_system,
-    object::{move_objects_system, ObjectSystem},
+    object::move_objects_system,
+    physics::{on_physics_update, PhysicsStage, PhysicsSystem},
     schedule::GameState,
 };
 use bevy::{prelude::*, time::FixedTimestep};
@@ -21,38 +19,49 @@ use bevy::{prelude::*, time::FixedTimestep};
 #[derive(Debug, PartialEq, Eq, Clone, Hash, SystemLabel)]
 pub struct PlayerSystem;
 
+#[derive(Debug, PartialEq, Eq, Clone, Hash, SystemLabel)]
+pub struct PlayerInputSystem;
+
 pub struct PlayerPlugin;
 
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<PlayerSizeIncreased>()
-            .insert_resource(PlayerHistory {
-                target_position: Vec3::ZERO,
-                new_position: Vec3::ZERO,
-                timer: Timer::new(Duration::from_secs_f32(0.2), true),
-            })
+            .init_resource::<CursorWorldPosition>()
+            .init_resource::<PlayerInput>()
             .add_system_set(
                 SystemSet::on_enter(GameState::BeforeInGame)
                     .label(PlayerSystem)
                     .with_system(spawn_player_system),
             )
+            .add_system_set(
+                SystemSet::on_update(GameState::InGame)
+                    .label(PlayerInputSystem)
+                    .before(PlayerSystem)
+                    .with_system(read_player_input_system),
+            )
             .add_system_set(
                 SystemSet::on_update(GameState::InGame)
                     .label(PlayerSystem)
-                    .with_system(move_player_system.before(rotate_player_system))
-                    .with_system(rotate_player_system.before(check_attachment_system))
-                    .with_system(check_attachment_system.after(ObjectSystem))
-                    .with_system(update_player_properties_system.after(check_attachment_system))
                     .with_system(remove_zap_effect_system)
-                    .with_system(shoot_player_zapper_system)
-                    .with_system(shoot_player_cannon_system)
+                    .with_system(check_player_death_system),
+            )
+            // The ship changes once per physics step, so replays don't depend on the frame rate
+            .add_system_set_to_stage(
+                PhysicsStage,
+                on_physics_update(GameState::InGame)
+                    .label(PlayerSystem)
+                    .after(PhysicsSystem::Propagate)
+                    .with_system(check_attachment_system)
+                    // Before attaching, as the nodes attached this step don't have their cell yet
+                    .with_system(detach_severed_nodes_system.before(check_attachment_system))
+                    .with_system(update_player_properties_system.after(check_attachment_system))
+                    .with_system(check_hits_system)
                     .with_system(
-                        check_hits_system
-                            .after(shoot_player_zapper_system)
-                            .after(shoot_player_cannon_system),
-                    )
-                    .with_system(check_player_death_system.after(check_hits_system))
-                    .with_system(update_player_history_system.before(shoot_zappy_enemy_system)),
+                        update_ship_health_system
+                            .after(check_hits_system)
+                            .after(detach_severed_nodes_system),
+                    ),
             )
             .add_system_set(
                 SystemSet::on_exit(GameState::AfterInGame)
@@ -62,8 +71,19 @@ impl Plugin for PlayerPlugin {
             .add_system_set(
                 SystemSet::on_enter(GameState::AfterInGame).with_system(explode_player_system),
             )
-            .add_system_set(
-                SystemSet::on_update(GameState::AfterInGame).with_system(move_objects_system),
+            .add_system_set_to_stage(
+                PhysicsStage,
+                on_physics_update(GameState::InGame)
+                    .label(PhysicsSystem::Movement)
+                    .with_system(move_player_system.before(rotate_player_system))
+                    .with_system(rotate_player_system),
+            )
+            // Let the pieces of the exploded player drift off
+            .add_system_set_to_stage(
+                PhysicsStage,
+                on_physics_update(GameState::AfterInGame)
+                    .label(PhysicsSystem::Movement)
+                    .with_system(move_objects_system),
             )
             .add_system_set(
                 SystemSet::new()
@@ -1,14 +1,14 @@
 use bevy::prelude::*;
 
-pub struct PlayerHistory {
-    pub target_position: Vec3,
-    pub new_position: Vec3,
-    pub timer: Timer,
-}
+/// The world position of the cursor, or `None` if there is no cursor to aim with.
+#[derive(Default)]
+pub struct CursorWorldPosition(pub Option<Vec3>);
 
-impl PlayerHistory {
-    pub fn update_position(&mut self, new_position: Vec3) {
-        self.target_position = self.new_position;
-        self.new_position = new_position;
-    }
+/// The inputs of the player in the current frame, read either from the devices or from a replay.
+#[derive(Default)]
+pub struct PlayerInput {
+    pub movement: Vec2,
+    pub aim: Option<Vec3>,
+    pub pause: bool,
+    pub give_up: bool,
 }
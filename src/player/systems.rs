@@ -1,28 +1,40 @@
-use std::time::Duration;
-
-use super::{constants::PLAYER_SPEED, Player, PlayerHistory, PlayerRoot, PlayerSizeIncreased};
+use super::{
+    constants::{ATTACHMENT_MARGIN, PLAYER_CORE_HEALTH, PLAYER_SPEED, SEVERED_NODE_SPEED},
+    CursorWorldPosition, Player, PlayerCore, PlayerInput, PlayerRoot, PlayerSizeIncreased,
+};
 use crate::colors::{COLOR_ENEMY, COLOR_PLAYER};
 use crate::{
     asset::SpriteHandles,
     audio::{AudioEvent, AudioType, PriorityAudioEvent, PriorityAudioType},
-    camera::MainCamera,
-    components::{
-        Bullet, Cannon, Collider, Projectile, Properties, ShieldForcefield, Velocity, ZapEffect,
-        Zapper,
-    },
+    collision::colliders_overlap,
+    components::{Collider, Health, Properties, Resistances, Velocity, ZapEffect},
     enemy::{Enemy, EnemyRoot},
-    events::Hit,
+    events::{DamageApplied, Hit},
+    faction::Faction,
+    grid::{GridCell, ShipGrid},
     object::Object,
+    physics::PHYSICS_TIMESTEP,
+    random::GameRng,
+    replay::ReplayMode,
     schedule::{GameState, ScheduleQueue},
+    spatial::SpatialIndex,
 };
-use bevy::{prelude::*, render::camera::RenderTarget};
+use bevy::prelude::*;
+use rand::Rng;
 
 pub fn spawn_player_system(mut commands: Commands, sprite_handles: Res<SpriteHandles>) {
     // Create a player that is on top of the root. This makes sure that we only need to attach to other non-root blocks and can query for the root-transform later on
     let player_root_entity = commands
         .spawn()
-        .insert(Collider)
+        .insert(Collider::default())
         .insert(Player)
+        .insert(PlayerCore)
+        .insert(Faction::Player)
+        .insert(GridCell(IVec2::ZERO))
+        .insert(Health {
+            current: PLAYER_CORE_HEALTH,
+            max: PLAYER_CORE_HEALTH,
+        })
         .insert_bundle(SpriteBundle {
             transform: Transform {
                 translation: Vec3::new(0.0, 0.0, 0.0),
@@ -33,15 +45,22 @@ pub fn spawn_player_system(mut commands: Commands, sprite_handles: Res<SpriteHan
         })
         .id();
 
+    // The core is the first cell of the grid, everything else attaches around it
+    let mut grid = ShipGrid::default();
+    grid.occupy(IVec2::ZERO, player_root_entity);
+
     // Init the player entity
     commands
         .spawn()
-        .insert(Collider)
+        .insert(Collider::default())
         .insert(Player)
         .insert(PlayerRoot { dist: 1.0 })
+        .insert(Faction::Player)
+        .insert(Velocity::default())
+        .insert(grid)
         .insert(Properties {
             size: 1,
-            health: 100,
+            health: PLAYER_CORE_HEALTH,
         })
         .insert_bundle(SpriteBundle {
             transform: Transform {
@@ -54,58 +73,67 @@ pub fn spawn_player_system(mut commands: Commands, sprite_handles: Res<SpriteHan
         .add_child(player_root_entity);
 }
 
-// Systems can query data in an SQL-like fashion
-//
-// Update the player position
-pub fn move_player_system(
-    keyboard_input: Res<Input<KeyCode>>,
-    mut query: Query<&mut Transform, With<PlayerRoot>>,
-    time: Res<Time>,
+pub fn read_player_input_system(
+    mut keyboard_input: ResMut<Input<KeyCode>>,
+    cursor_position: Res<CursorWorldPosition>,
+    replay_mode: Res<ReplayMode>,
+    mut player_input: ResMut<PlayerInput>,
 ) {
-    // The player's movement directions
-    let mut movement_x = 0.;
-    let mut movement_y = 0.;
+    player_input.pause = keyboard_input.just_pressed(KeyCode::Escape);
 
-    let mut player_transform = query.single_mut();
+    // Clear the key, otherwise the paused state would be left again in the same frame
+    if player_input.pause {
+        keyboard_input.clear_just_pressed(KeyCode::Escape);
+    }
+
+    // A replay that is played back steers the ship instead, but can still be paused
+    if matches!(*replay_mode, ReplayMode::Playback { .. }) {
+        return;
+    }
+
+    // The player's movement directions
+    let mut movement = Vec2::ZERO;
 
     // Add the different directions. This way pressing left and right cancels out
     if keyboard_input.any_pressed([KeyCode::A, KeyCode::Left]) {
-        movement_x -= 1.;
+        movement.x -= 1.;
     }
     if keyboard_input.any_pressed([KeyCode::D, KeyCode::Right]) {
-        movement_x += 1.;
+        movement.x += 1.;
     }
     if keyboard_input.any_pressed([KeyCode::S, KeyCode::Down]) {
-        movement_y -= 1.;
+        movement.y -= 1.;
     }
     if keyboard_input.any_pressed([KeyCode::W, KeyCode::Up]) {
-        movement_y += 1.;
+        movement.y += 1.;
     }
 
-    // Move the player and clamp it to the screen
-    player_transform.translation.x += movement_x * PLAYER_SPEED * time.delta_seconds();
-    player_transform.translation.y += movement_y * PLAYER_SPEED * time.delta_seconds();
+    player_input.movement = movement;
+    player_input.aim = cursor_position.0;
+    player_input.give_up = keyboard_input.just_pressed(KeyCode::G);
+}
+
+// Systems can query data in an SQL-like fashion
+//
+// Update the player position
+pub fn move_player_system(
+    player_input: Res<PlayerInput>,
+    mut query: Query<&mut Transform, With<PlayerRoot>>,
+) {
+    let mut player_transform = query.single_mut();
+
+    // Move the player
+    player_transform.translation.x += player_input.movement.x * PLAYER_SPEED * PHYSICS_TIMESTEP;
+    player_transform.translation.y += player_input.movement.y * PLAYER_SPEED * PHYSICS_TIMESTEP;
 }
 
 pub fn rotate_player_system(
-    windows: Res<Windows>,
+    player_input: Res<PlayerInput>,
     mut player_query: Query<(&mut Transform, &Properties), With<PlayerRoot>>,
-    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
 ) {
-    if let Some(cursor_position) = windows.primary().cursor_position() {
-        let (camera, camera_transform) = camera_query.single();
+    if let Some(world_pos) = player_input.aim {
         let (mut player_transform, player_stats) = player_query.single_mut();
 
-        let window = if let RenderTarget::Window(id) = camera.target {
-            windows.get(id).unwrap()
-        } else {
-            windows.get_primary().unwrap()
-        };
-
-        let window_size = Vec2::new(window.width() as f32, window.height() as f32);
-        let ndc = (cursor_position / window_size) * 2.0 - Vec2::ONE;
-        let ndc_to_world = camera_transform.compute_matrix() * camera.projection_matrix().inverse();
-        let world_pos = ndc_to_world.project_point3(ndc.extend(-1.0));
         let difference = world_pos - player_transform.translation;
         let angle = difference.y.atan2(difference.x);
         let new_rotation = Quat::from_axis_angle(Vec3::new(0., 0., 1.), angle);
@@ -116,182 +144,96 @@ pub fn rotate_player_system(
     }
 }
 
-//#####################
-// PLAYER TURRETS
-//#####################
-pub fn shoot_player_zapper_system(
+#[allow(clippy::type_complexity)]
+pub fn check_hits_system(
     mut commands: Commands,
-    time: Res<Time>,
-    mut event_hit: EventWriter<Hit>,
+    mut event_hit: EventReader<Hit>,
+    mut event_damage_applied: EventWriter<DamageApplied>,
     mut event_audio: EventWriter<AudioEvent>,
-    mut zapper_query: Query<(&GlobalTransform, &mut Zapper), With<Player>>,
-    shootable_query: Query<
-        (&GlobalTransform, Entity, &Parent),
-        (With<Enemy>, Without<ShieldForcefield>),
-    >,
+    mut node_query: Query<(
+        &mut Health,
+        &mut Sprite,
+        Option<&Resistances>,
+        Option<&Parent>,
+        Option<&Player>,
+        Option<&PlayerCore>,
+        Option<&EnemyRoot>,
+    )>,
+    mut ship_query: Query<(&mut Velocity, &Properties)>,
 ) {
-    for (zapper_transform, mut zapper_stats) in zapper_query.iter_mut() {
-        if zapper_stats.cooldown_timer > 0. {
-            zapper_stats.cooldown_timer -= time.delta_seconds();
-        } else {
-            for (shootable_transform, _shootable_entity, shootable_parent) in shootable_query.iter()
-            {
-                let distance = zapper_transform
-                    .compute_transform()
-                    .translation
-                    .distance(shootable_transform.compute_transform().translation);
-                // If there is a hit
-                if distance < zapper_stats.range {
-                    zapper_stats.cooldown_timer = zapper_stats.fire_rate;
-                    event_hit.send(Hit {
-                        target: shootable_parent.get(),
-                        damage: zapper_stats.damage,
-                    });
-                    event_audio.send(AudioEvent(AudioType::Laser));
-                    event_audio.send(AudioEvent(AudioType::Hit));
-
-                    // Draw a yellow rectangle between the target and the zapper
-                    let zapper_computed_transform = zapper_transform.compute_transform();
-
-                    // Draw squares, interpolated between the two points
-                    for i in 1..distance.floor() as i32 {
-                        let t = i as f32 / distance.floor();
-                        let x = zapper_computed_transform.translation.x
-                            + (shootable_transform.compute_transform().translation.x
-                                - zapper_computed_transform.translation.x)
-                                * t;
-                        let y = zapper_computed_transform.translation.y
-                            + (shootable_transform.compute_transform().translation.y
-                                - zapper_computed_transform.translation.y)
-                                * t;
-
-                        commands
-                            .spawn()
-                            .insert_bundle(SpriteBundle {
-                                transform: Transform {
-                                    translation: Vec3::new(x, y, 0.),
-                                    scale: Vec3::new(2., 2., 0.),
-                                    ..default()
-                                },
-                                sprite: Sprite {
-                                    color: COLOR_PLAYER,
-                                    ..default()
-                                },
-                                ..default()
-                            })
-                            .insert(ZapEffect);
-                    }
-
-                    // Only one shot per cooldown
-                    break;
-                }
-            }
+    for hit in event_hit.iter() {
+        let (mut health, mut sprite, resistances, parent, is_player, player_core, enemy_core) =
+            match node_query.get_mut(hit.target) {
+                Ok(node) => node,
+                Err(_) => continue,
+            };
+        // Already destroyed by an earlier hit
+        if health.current == 0 {
+            continue;
         }
-    }
-}
 
-pub fn shoot_player_cannon_system(
-    mut commands: Commands,
-    time: Res<Time>,
-    mut event_hit: EventWriter<Hit>,
-    mut event_audio: EventWriter<AudioEvent>,
-    mut cannon_query: Query<(&GlobalTransform, &mut Cannon), With<Player>>,
-    shootable_query: Query<
-        (&GlobalTransform, Entity, &Parent),
-        (With<Enemy>, Without<ShieldForcefield>),
-    >,
-) {
-    for (cannon_transform, mut cannon_stats) in cannon_query.iter_mut() {
-        if cannon_stats.cooldown_timer > 0. {
-            cannon_stats.cooldown_timer -= time.delta_seconds();
+        // Knock back the whole ship, the node is either its root or a child of it
+        let ship = parent.map_or(hit.target, |parent| parent.get());
+        if let Ok((mut velocity, properties)) = ship_query.get_mut(ship) {
+            let impulse = hit.impulse / properties.size.max(1) as f32;
+            velocity.x += impulse.x;
+            velocity.y += impulse.y;
+        }
+
+        // Cores have no resistances and take the full damage
+        let damage = resistances.map_or(hit.damage, |resistances| {
+            resistances.mitigate(hit.damage, hit.damage_type)
+        });
+        health.current = health.current.saturating_sub(damage);
+        event_damage_applied.send(DamageApplied {
+            damage,
+            damage_type: hit.damage_type,
+            target: hit.target,
+            source: hit.source,
+            position: hit.position,
+            weapon: hit.weapon,
+        });
+        // Tint the node that got hit, red for the player and yellow for enemies
+        sprite.color = if is_player.is_some() {
+            COLOR_ENEMY
         } else {
-            for (shootable_transform, _shootable_entity, shootable_parent) in shootable_query.iter()
-            {
-                let distance = cannon_transform
-                    .compute_transform()
-                    .translation
-                    .distance(shootable_transform.compute_transform().translation);
-                // If there is a hit
-
-                if distance < cannon_stats.range {
-                    cannon_stats.cooldown_timer = cannon_stats.fire_rate;
-                    event_hit.send(Hit {
-                        target: shootable_parent.get(),
-                        damage: cannon_stats.damage,
-                    });
-
-                    event_audio.send(AudioEvent(AudioType::Explosion));
-
-                    let velocity_x: f32 = (shootable_transform.compute_transform().translation.x
-                        - cannon_transform.compute_transform().translation.x)
-                        / distance;
-                    let velocity_y: f32 = (shootable_transform.compute_transform().translation.y
-                        - cannon_transform.compute_transform().translation.y)
-                        / distance;
-
-                    // Shoot a bullet
-                    commands
-                        .spawn()
-                        .insert_bundle(SpriteBundle {
-                            transform: Transform {
-                                translation: cannon_transform.compute_transform().translation,
-                                scale: Vec3::new(2., 2., 0.),
-                                ..default()
-                            },
-                            sprite: Sprite {
-                                color: COLOR_PLAYER,
-                                ..default()
-                            },
-                            ..default()
-                        })
-                        .insert(Projectile {})
-                        .insert(Bullet {
-                            damage: cannon_stats.damage,
-                            enemy: false,
-                        })
-                        .insert(Velocity {
-                            x: velocity_x * 2.,
-                            y: velocity_y * 2.,
-                            rotation: 0.,
-                        });
-
-                    // Only one shot per cooldown
-                    break;
-                }
-            }
+            COLOR_PLAYER
+        };
+
+        // Cores stay around, their whole ship is destroyed with them
+        if health.current == 0 && player_core.is_none() && enemy_core.is_none() {
+            event_audio.send(AudioEvent(AudioType::Explosion));
+            commands.entity(hit.target).despawn_recursive();
+
+            // Flash where the hit landed
+            commands
+                .spawn()
+                .insert_bundle(SpriteBundle {
+                    transform: Transform {
+                        translation: hit.position.extend(0.),
+                        scale: Vec3::new(8., 8., 0.),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .insert(ZapEffect);
         }
     }
 }
 
-#[allow(clippy::type_complexity)]
-pub fn check_hits_system(
-    mut event_hit: EventReader<Hit>,
-    mut player_query: Query<(&mut Properties, Entity), (With<PlayerRoot>, Without<EnemyRoot>)>,
-    mut element_query: Query<
-        (&mut Sprite, Option<&Player>, &Parent),
-        (Without<PlayerRoot>, Without<EnemyRoot>),
-    >,
-    mut enemy_query: Query<(&mut Properties, Entity), (With<EnemyRoot>, Without<PlayerRoot>)>,
+// The health of a ship is the health of all its nodes together
+pub fn update_ship_health_system(
+    mut ship_query: Query<(&mut Properties, Option<&Health>, Option<&Children>)>,
+    health_query: Query<&Health>,
 ) {
-    let (mut player_stats, player_entity) = player_query.single_mut();
-    for hit in event_hit.iter() {
-        if hit.target == player_entity {
-            player_stats.health = player_stats.health.saturating_sub(hit.damage);
-            // Tint player red
-            for (mut sprite, is_player, _) in element_query.iter_mut() {
-                if is_player.is_some() {
-                    sprite.color = COLOR_ENEMY;
-                }
-            }
-        } else if let Ok((mut enemy_properties, _)) = enemy_query.get_mut(hit.target) {
-            enemy_properties.health = enemy_properties.health.saturating_sub(hit.damage);
-            // Tint enemy yellow
-            for (mut sprite, _, parent) in element_query.iter_mut() {
-                if parent.get() == hit.target {
-                    sprite.color = COLOR_PLAYER;
-                }
-            }
-        }
+    for (mut properties, own_health, children) in ship_query.iter_mut() {
+        let children_health: u32 = children
+            .iter()
+            .flat_map(|children| children.iter())
+            .filter_map(|child| health_query.get(*child).ok())
+            .map(|health| health.current)
+            .sum();
+        properties.health = own_health.map_or(0, |health| health.current) + children_health;
     }
 }
 
@@ -310,14 +252,14 @@ pub fn remove_zap_effect_system(mut commands: Commands, query: Query<Entity, Wit
 }
 
 pub fn check_player_death_system(
-    mut query: Query<&Properties, With<PlayerRoot>>,
+    core_query: Query<&Health, With<PlayerCore>>,
     mut game_state: ResMut<State<GameState>>,
     mut schedule_queue: ResMut<ScheduleQueue>,
     mut audio_events: EventWriter<PriorityAudioEvent>,
-    input: Res<Input<KeyCode>>,
+    player_input: Res<PlayerInput>,
 ) {
-    for properties in query.iter_mut() {
-        if properties.health == 0 || input.just_pressed(KeyCode::G) {
+    for core_health in core_query.iter() {
+        if core_health.current == 0 || player_input.give_up {
             audio_events.send(PriorityAudioEvent(PriorityAudioType::Death));
             game_state.set(GameState::AfterInGame).unwrap();
             schedule_queue.0.push_back(GameState::BeforeEndScreen);
@@ -332,68 +274,166 @@ pub fn check_player_death_system(
 #[allow(clippy::type_complexity)]
 pub fn check_attachment_system(
     mut commands: Commands,
-    player_query: Query<&GlobalTransform, (With<Player>, Without<PlayerRoot>)>,
-    mut player_root_query: Query<(Entity, &mut PlayerRoot, &Transform), With<PlayerRoot>>,
+    spatial_index: Res<SpatialIndex>,
+    player_query: Query<(&GlobalTransform, &Collider), (With<Player>, Without<PlayerRoot>)>,
+    mut player_root_query: Query<
+        (Entity, &mut PlayerRoot, &mut ShipGrid, &Transform),
+        With<PlayerRoot>,
+    >,
     mut attachable_query: Query<
-        (Entity, &mut Transform),
+        (&mut Transform, &GlobalTransform, &Collider),
         (With<Object>, Without<Player>, Without<PlayerRoot>),
     >,
     mut event_writer: EventWriter<PlayerSizeIncreased>,
 ) {
     // We need the transform of the root, since everything is relative to it and when adding children we need to revert it first
-    let (root_entity, mut root_component, root_transform) =
+    let (root_entity, mut root_component, mut root_grid, root_transform) =
         player_root_query.get_single_mut().unwrap();
 
-    for player_global_transform in &player_query {
-        for (attachable_entity, mut attachable_transform) in attachable_query.iter_mut() {
-            let distance = player_global_transform
-                .compute_transform()
-                .translation
-                .distance(attachable_transform.translation);
-
-            // TODO: More exact collision detection
-            if distance
-                < ((player_global_transform.compute_transform().scale.x
-                    + attachable_transform.scale.x)
-                    / 2.)
-                    * 8.
-            // The sprites go past the scale of the object, which is just 1x1 with an 8x8 sprite
-            {
-                // We only check for attachments using the children, since we created a seperate child
-                // on top of the root in the beginning
-                commands.entity(root_entity).add_child(attachable_entity);
-                commands.entity(attachable_entity).insert(Player);
-                commands.entity(attachable_entity).remove::<Object>();
-
-                // Calculate distance to center of root
-                // Should this be global transform?
-                let total_dist = root_transform
-                    .translation
-                    .distance(attachable_transform.translation);
-                if total_dist > root_component.dist {
-                    root_component.dist = total_dist;
-                }
-
-                // The new translations are offsets from the parent
-                let x = attachable_transform.translation.x - root_transform.translation.x;
-                let y = attachable_transform.translation.y - root_transform.translation.y;
-
-                attachable_transform.translation = Vec3::new(x, y, 0.0);
-
-                // We need to flip the `Z` coordinate here so that things that connect
-                // at the top will stay at the top and won't get rotated and stick to the bottom.
-                let euler = root_transform.rotation.to_euler(EulerRot::XYZ);
-                attachable_transform.rotate_around(
-                    Vec3::ZERO,
-                    Quat::from_euler(EulerRot::XYZ, euler.0, euler.1, -euler.2),
-                );
-
-                event_writer.send(PlayerSizeIncreased);
+    // An object can be close to several nodes, but must only be attached once
+    let mut attached = Vec::new();
+
+    for (player_global_transform, player_collider) in &player_query {
+        let player_transform = player_global_transform.compute_transform();
+        // Objects attach when they touch the node, or almost do
+        let reach_collider = player_collider.inflated(ATTACHMENT_MARGIN);
+
+        for (attachable_entity, _) in spatial_index.query_colliders(
+            player_transform.translation.truncate(),
+            reach_collider.bounding_radius(player_transform.scale),
+        ) {
+            if attached.contains(&attachable_entity) {
+                continue;
+            }
+            let (mut attachable_transform, attachable_global_transform, attachable_collider) =
+                match attachable_query.get_mut(attachable_entity) {
+                    Ok(attachable) => attachable,
+                    Err(_) => continue,
+                };
+
+            // Both in world space, like the spatial index
+            if !colliders_overlap(
+                &reach_collider,
+                &player_transform,
+                attachable_collider,
+                &attachable_global_transform.compute_transform(),
+            ) {
+                continue;
             }
+
+            // Into the space of the root, where the grid is, so it turns with the ship. Loose
+            // objects and the root have no parent, so their transforms are in world space too
+            let inverse_root_rotation = root_transform.rotation.inverse();
+            let local_position = (inverse_root_rotation
+                * (attachable_transform.translation - root_transform.translation))
+                .truncate();
+            let (_, _, local_rotation) =
+                (inverse_root_rotation * attachable_transform.rotation).to_euler(EulerRot::XYZ);
+
+            // Objects that would only fit into a taken cell stay loose for now
+            let cell = match root_grid.nearest_free_cell(local_position) {
+                Some(cell) => cell,
+                None => continue,
+            };
+            root_grid.occupy(cell, attachable_entity);
+            attached.push(attachable_entity);
+
+            // We only check for attachments using the children, since we created a seperate child
+            // on top of the root in the beginning
+            commands.entity(root_entity).add_child(attachable_entity);
+            commands
+                .entity(attachable_entity)
+                .insert(Player)
+                .insert(Faction::Player)
+                .insert(GridCell(cell))
+                .remove::<Object>();
+
+            // The new translations are offsets from the parent
+            let cell_position = ShipGrid::cell_position(cell);
+            attachable_transform.translation = cell_position.extend(0.);
+            attachable_transform.rotation =
+                Quat::from_rotation_z(ShipGrid::snap_rotation(local_rotation));
+
+            // Calculate distance to center of root
+            let total_dist = cell_position.length();
+            if total_dist > root_component.dist {
+                root_component.dist = total_dist;
+            }
+
+            event_writer.send(PlayerSizeIncreased {
+                position: root_transform
+                    .transform_point(cell_position.extend(0.))
+                    .truncate(),
+            });
         }
     }
 }
 
+// Nodes that are gone free their cell, and everything that is no longer connected
+// to the core through its neighbours breaks off and drifts away as debris
+#[allow(clippy::type_complexity)]
+pub fn detach_severed_nodes_system(
+    mut commands: Commands,
+    mut rng: ResMut<GameRng>,
+    mut root_query: Query<
+        (
+            Entity,
+            &mut PlayerRoot,
+            &mut ShipGrid,
+            &mut Properties,
+            &GlobalTransform,
+        ),
+        With<PlayerRoot>,
+    >,
+    mut node_query: Query<
+        (&GlobalTransform, &mut Transform),
+        (With<GridCell>, With<Player>, Without<PlayerRoot>),
+    >,
+) {
+    let (root_entity, mut root_component, mut grid, mut properties, root_global_transform) =
+        match root_query.get_single_mut() {
+            Ok(root) => root,
+            Err(_) => return,
+        };
+
+    if !grid.retain(|entity| node_query.contains(entity)) {
+        return;
+    }
+
+    for (cell, entity) in grid.disconnected_from(IVec2::ZERO) {
+        grid.free(cell);
+
+        let (global_transform, mut transform) = node_query.get_mut(entity).unwrap();
+        let world_transform = global_transform.compute_transform();
+        let direction = (world_transform.translation - root_global_transform.translation())
+            .truncate()
+            .normalize_or_zero();
+
+        commands.entity(root_entity).remove_children(&[entity]);
+        commands
+            .entity(entity)
+            .remove::<Player>()
+            .remove::<Faction>()
+            .remove::<GridCell>()
+            .insert(Object)
+            .insert(Velocity {
+                x: direction.x * SEVERED_NODE_SPEED,
+                y: direction.y * SEVERED_NODE_SPEED,
+                rotation: rng.gen::<f32>() * 2. - 1.,
+            });
+
+        // No longer relative to the root
+        *transform = world_transform;
+    }
+
+    // The ship only consists of what is left on the grid
+    properties.size = grid.cells().count() as u32;
+    root_component.dist = grid
+        .cells()
+        .map(|(cell, _)| ShipGrid::cell_position(cell).length())
+        .fold(1., f32::max);
+}
+
 pub fn update_player_properties_system(
     mut query: Query<&mut Properties, With<PlayerRoot>>,
     event_reader: EventReader<PlayerSizeIncreased>,
@@ -404,26 +444,12 @@ pub fn update_player_properties_system(
 
     if player_size_increase > 0 {
         properties.size += player_size_increase;
-        properties.health += player_size_increase * 10;
-    }
-}
-
-pub fn update_player_history_system(
-    query: Query<&Transform, With<PlayerRoot>>,
-    mut player_history: ResMut<PlayerHistory>,
-    time: Res<Time>,
-) {
-    player_history
-        .timer
-        .tick(Duration::from_secs_f32(time.delta_seconds()));
-    if player_history.timer.just_finished() {
-        let transform = query.single();
-        player_history.update_position(transform.translation);
     }
 }
 
 pub fn explode_player_system(
     mut commands: Commands,
+    mut rng: ResMut<GameRng>,
     mut player_elements: Query<
         (Entity, &GlobalTransform, &mut Transform),
         (With<Player>, Without<PlayerRoot>),
@@ -436,11 +462,12 @@ pub fn explode_player_system(
             .entity(entity)
             .remove::<Parent>()
             .remove::<Player>()
+            .remove::<Faction>()
             .insert(Object)
             .insert(Velocity {
-                x: rand::random::<f32>() * 6. - 3.,
-                y: rand::random::<f32>() * 6. - 3.,
-                rotation: rand::random::<f32>() * 0.2,
+                x: rng.gen::<f32>() * 360. - 180.,
+                y: rng.gen::<f32>() * 360. - 180.,
+                rotation: rng.gen::<f32>() * 12.,
             });
 
         transform.translation = old_global_transform;
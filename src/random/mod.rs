@@ -0,0 +1,8 @@
+mod plugin;
+mod resources;
+mod systems;
+
+pub use self::{
+    plugin::{RandomPlugin, RandomSystem},
+    resources::GameRng,
+};
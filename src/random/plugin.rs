@@ -0,0 +1,21 @@
+use super::{systems::reseed_rng_system, GameRng};
+use crate::schedule::GameState;
+use bevy::prelude::*;
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash, SystemLabel)]
+pub struct RandomSystem;
+
+pub struct RandomPlugin {
+    /// Use the same seed for every run instead of picking a new one.
+    pub seed: Option<u64>,
+}
+
+impl Plugin for RandomPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(GameRng::new(self.seed)).add_system_set(
+            SystemSet::on_enter(GameState::BeforeInGame)
+                .label(RandomSystem)
+                .with_system(reseed_rng_system),
+        );
+    }
+}
@@ -0,0 +1,60 @@
+use rand::{rngs::StdRng, SeedableRng};
+use std::ops::{Deref, DerefMut};
+
+/// The random number generator every gameplay system draws from.
+///
+/// It is reseeded at the start of every run, so a run with the same seed and inputs
+/// plays out identically.
+pub struct GameRng {
+    seed: u64,
+    fixed_seed: Option<u64>,
+    queued_seed: Option<u64>,
+    rng: StdRng,
+}
+
+impl GameRng {
+    /// Creates a new generator. If `fixed_seed` is `None` every run picks a new seed.
+    pub fn new(fixed_seed: Option<u64>) -> Self {
+        let seed = fixed_seed.unwrap_or_else(rand::random);
+
+        Self {
+            seed,
+            fixed_seed,
+            queued_seed: None,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// The seed of the current run.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Use `seed` for the next run only, e.g. to play back a replay.
+    pub fn queue_seed(&mut self, seed: u64) {
+        self.queued_seed = Some(seed);
+    }
+
+    pub fn reseed(&mut self) {
+        self.seed = self
+            .queued_seed
+            .take()
+            .or(self.fixed_seed)
+            .unwrap_or_else(rand::random);
+        self.rng = StdRng::seed_from_u64(self.seed);
+    }
+}
+
+impl Deref for GameRng {
+    type Target = StdRng;
+
+    fn deref(&self) -> &Self::Target {
+        &self.rng
+    }
+}
+
+impl DerefMut for GameRng {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.rng
+    }
+}
@@ -0,0 +1,7 @@
+use super::GameRng;
+use bevy::prelude::*;
+
+pub fn reseed_rng_system(mut rng: ResMut<GameRng>) {
+    rng.reseed();
+    info!("Starting run with seed {}", rng.seed());
+}
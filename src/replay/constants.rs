@@ -0,0 +1,3 @@
+// File
+pub const REPLAY_PATH: &str = "replay.ron";
+pub const REPLAY_VERSION: u32 = 2;
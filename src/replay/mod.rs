@@ -0,0 +1,10 @@
+mod constants;
+mod plugin;
+mod resources;
+mod systems;
+
+pub use self::{
+    constants::REPLAY_PATH,
+    plugin::{ReplayPlugin, ReplaySystem},
+    resources::{Replay, ReplayMode},
+};
@@ -0,0 +1,58 @@
+use super::{
+    resources::{Replay, ReplayMode},
+    systems::{
+        finish_replay_system, play_back_player_input_system, queue_playback_seed_system,
+        record_player_input_system, start_recording_system,
+    },
+};
+use crate::{
+    physics::{on_physics_update, PhysicsStage, PhysicsSystem},
+    random::RandomSystem,
+    schedule::GameState,
+};
+use bevy::prelude::*;
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash, SystemLabel)]
+pub struct ReplaySystem;
+
+/// Records the inputs of every run and plays them back on request.
+pub struct ReplayPlugin {
+    /// A replay to play back in the first run.
+    pub playback: Option<Replay>,
+}
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(
+            self.playback
+                .clone()
+                .map_or_else(ReplayMode::default, ReplayMode::playback),
+        )
+        .add_system_set(
+            SystemSet::on_enter(GameState::BeforeInGame)
+                .label(ReplaySystem)
+                .before(RandomSystem)
+                .with_system(queue_playback_seed_system),
+        )
+        .add_system_set(
+            SystemSet::on_enter(GameState::BeforeInGame)
+                .label(ReplaySystem)
+                .after(RandomSystem)
+                .with_system(start_recording_system),
+        )
+        // Once per physics step, so a replay doesn't depend on the frame rate
+        .add_system_set_to_stage(
+            PhysicsStage,
+            on_physics_update(GameState::InGame)
+                .label(ReplaySystem)
+                .before(PhysicsSystem::Movement)
+                .with_system(play_back_player_input_system)
+                .with_system(record_player_input_system.after(play_back_player_input_system)),
+        )
+        .add_system_set(
+            SystemSet::on_enter(GameState::AfterInGame)
+                .label(ReplaySystem)
+                .with_system(finish_replay_system),
+        );
+    }
+}
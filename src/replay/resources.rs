@@ -0,0 +1,148 @@
+use super::constants::REPLAY_VERSION;
+use crate::{
+    player::PlayerInput,
+    ron_file::{load_ron_file, save_ron_file},
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// The inputs of a single physics step of a run. Pausing and giving up end the steps
+/// before they could be recorded, so only the steering is.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct ReplayFrame {
+    pub movement: [f32; 2],
+    pub aim: Option<[f32; 2]>,
+}
+
+impl ReplayFrame {
+    pub fn apply(&self, player_input: &mut PlayerInput) {
+        player_input.movement = Vec2::from(self.movement);
+        player_input.aim = self.aim.map(|aim| Vec2::from(aim).extend(0.));
+    }
+}
+
+impl From<&PlayerInput> for ReplayFrame {
+    fn from(player_input: &PlayerInput) -> Self {
+        Self {
+            movement: player_input.movement.into(),
+            aim: player_input.aim.map(|aim| aim.truncate().into()),
+        }
+    }
+}
+
+/// The seed and the per-step inputs of a run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Replay {
+    pub version: u32,
+    pub seed: u64,
+    pub frames: Vec<ReplayFrame>,
+}
+
+impl Replay {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            version: REPLAY_VERSION,
+            seed,
+            frames: Vec::new(),
+        }
+    }
+
+    pub fn load(path: &str) -> Option<Self> {
+        let replay: Self = load_ron_file(path)?;
+
+        if replay.version != REPLAY_VERSION {
+            warn!(
+                "Replay {} has version {}, expected {}",
+                path, replay.version, REPLAY_VERSION
+            );
+            return None;
+        }
+
+        Some(replay)
+    }
+
+    pub fn save(&self, path: &str) {
+        save_ron_file(self, path);
+    }
+}
+
+/// Whether the current run is recorded or played back from a replay.
+#[derive(Debug, Default)]
+pub enum ReplayMode {
+    #[default]
+    Idle,
+    Recording(Replay),
+    Playback {
+        replay: Replay,
+        frame: usize,
+    },
+}
+
+impl ReplayMode {
+    pub fn playback(replay: Replay) -> Self {
+        Self::Playback { replay, frame: 0 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ron_file::temp_path;
+    use std::fs;
+
+    fn replay() -> Replay {
+        let mut replay = Replay::new(7);
+        replay.frames.push(ReplayFrame {
+            movement: [1., 0.],
+            aim: None,
+        });
+        replay.frames.push(ReplayFrame {
+            movement: [0., -1.],
+            aim: Some([30., 4.5]),
+        });
+        replay
+    }
+
+    #[test]
+    fn replays_load_as_they_were_saved() {
+        let path = temp_path("replay_round_trip");
+        replay().save(&path);
+        let loaded = Replay::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.seed, 7);
+        assert_eq!(loaded.frames.len(), 2);
+        assert_eq!(loaded.frames[0].movement, [1., 0.]);
+        assert_eq!(loaded.frames[0].aim, None);
+        assert_eq!(loaded.frames[1].movement, [0., -1.]);
+        assert_eq!(loaded.frames[1].aim, Some([30., 4.5]));
+    }
+
+    #[test]
+    fn replays_of_another_version_are_not_loaded() {
+        let path = temp_path("replay_version");
+        Replay {
+            version: REPLAY_VERSION + 1,
+            ..replay()
+        }
+        .save(&path);
+        let loaded = Replay::load(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert!(loaded.is_none());
+    }
+
+    #[test]
+    fn frames_carry_the_steering_over_to_the_input() {
+        let input = PlayerInput {
+            movement: Vec2::new(-1., 1.),
+            aim: Some(Vec3::new(5., 6., 0.)),
+            ..default()
+        };
+        let mut played_back = PlayerInput::default();
+        ReplayFrame::from(&input).apply(&mut played_back);
+
+        assert_eq!(played_back.movement, input.movement);
+        assert_eq!(played_back.aim, input.aim);
+    }
+}
@@ -0,0 +1,119 @@
+use super::{
+    constants::REPLAY_PATH,
+    resources::{Replay, ReplayFrame, ReplayMode},
+};
+use crate::{player::PlayerInput, random::GameRng};
+use bevy::prelude::*;
+
+// A replay only plays out the same if it starts with the same seed
+pub fn queue_playback_seed_system(replay_mode: Res<ReplayMode>, mut rng: ResMut<GameRng>) {
+    if let ReplayMode::Playback { replay, .. } = &*replay_mode {
+        rng.queue_seed(replay.seed);
+    }
+}
+
+pub fn start_recording_system(mut replay_mode: ResMut<ReplayMode>, rng: Res<GameRng>) {
+    if !matches!(*replay_mode, ReplayMode::Playback { .. }) {
+        *replay_mode = ReplayMode::Recording(Replay::new(rng.seed()));
+    }
+}
+
+pub fn play_back_player_input_system(
+    mut replay_mode: ResMut<ReplayMode>,
+    mut player_input: ResMut<PlayerInput>,
+) {
+    if let ReplayMode::Playback { replay, frame } = &mut *replay_mode {
+        if let Some(replay_frame) = replay.frames.get(*frame) {
+            replay_frame.apply(&mut player_input);
+            *frame += 1;
+        } else {
+            // The replay is over, so end the run as if the player gave up
+            *player_input = PlayerInput {
+                give_up: true,
+                ..default()
+            };
+        }
+    }
+}
+
+pub fn record_player_input_system(
+    mut replay_mode: ResMut<ReplayMode>,
+    player_input: Res<PlayerInput>,
+) {
+    if let ReplayMode::Recording(replay) = &mut *replay_mode {
+        replay.frames.push(ReplayFrame::from(&*player_input));
+    }
+}
+
+pub fn finish_replay_system(mut replay_mode: ResMut<ReplayMode>) {
+    if let ReplayMode::Recording(replay) = std::mem::take(&mut *replay_mode) {
+        replay.save(REPLAY_PATH);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        components::Properties,
+        player::{move_player_system, rotate_player_system, PlayerRoot},
+    };
+
+    // Runs the replay and movement systems for one step per input, as the physics stage does
+    fn run_steps(replay_mode: ReplayMode, inputs: &[PlayerInput]) -> (Transform, ReplayMode) {
+        let mut world = World::new();
+        world.insert_resource(replay_mode);
+        world.insert_resource(PlayerInput::default());
+        let player = world
+            .spawn()
+            .insert(PlayerRoot { dist: 1. })
+            .insert(Properties { size: 1, health: 1 })
+            .insert(Transform::default())
+            .id();
+
+        let mut stage = SystemStage::single_threaded()
+            .with_system(play_back_player_input_system)
+            .with_system(record_player_input_system.after(play_back_player_input_system))
+            .with_system(move_player_system.after(record_player_input_system))
+            .with_system(rotate_player_system.after(move_player_system));
+
+        for input in inputs {
+            {
+                let mut player_input = world.resource_mut::<PlayerInput>();
+                player_input.movement = input.movement;
+                player_input.aim = input.aim;
+            }
+            stage.run(&mut world);
+        }
+
+        let transform = *world.get::<Transform>(player).unwrap();
+        (transform, world.remove_resource::<ReplayMode>().unwrap())
+    }
+
+    #[test]
+    fn playback_ends_where_the_recording_did() {
+        let inputs: Vec<PlayerInput> = (0..120)
+            .map(|step| PlayerInput {
+                movement: Vec2::new((step % 7) as f32 - 3., (step % 5) as f32 - 2.)
+                    .clamp_length_max(1.),
+                aim: Some(Vec3::new(step as f32, 100. - step as f32, 0.)),
+                ..default()
+            })
+            .collect();
+
+        let (recorded, replay_mode) = run_steps(ReplayMode::Recording(Replay::new(0)), &inputs);
+        let replay = match replay_mode {
+            ReplayMode::Recording(replay) => replay,
+            _ => panic!("Not recording anymore"),
+        };
+        assert_eq!(replay.frames.len(), inputs.len());
+
+        // The devices don't steer while a replay is played back
+        let idle: Vec<PlayerInput> = inputs.iter().map(|_| PlayerInput::default()).collect();
+        let (played_back, _) = run_steps(ReplayMode::playback(replay), &idle);
+
+        assert_ne!(recorded.translation, Vec3::ZERO);
+        assert_eq!(played_back.translation, recorded.translation);
+        assert_eq!(played_back.rotation, recorded.rotation);
+    }
+}
@@ -0,0 +1,54 @@
+use bevy::prelude::*;
+use serde::{de::DeserializeOwned, Serialize};
+use std::fs;
+
+/// Reads and parses a RON file, logging a warning if either fails.
+pub fn load_ron_file<T: DeserializeOwned>(path: &str) -> Option<T> {
+    let contents = fs::read_to_string(path)
+        .map_err(|err| warn!("Failed to read {}: {}", path, err))
+        .ok()?;
+
+    ron::from_str(&contents)
+        .map_err(|err| warn!("Failed to parse {}: {}", path, err))
+        .ok()
+}
+
+/// Serializes and writes a RON file, logging a warning if either fails.
+pub fn save_ron_file<T: Serialize>(value: &T, path: &str) {
+    match ron::to_string(value) {
+        Ok(contents) => {
+            if let Err(err) = fs::write(path, contents) {
+                warn!("Failed to write {}: {}", path, err);
+            }
+        }
+        Err(err) => warn!("Failed to serialize {}: {}", path, err),
+    }
+}
+
+/// Reads and parses every file in a folder whose name ends with `extension`.
+pub fn load_ron_folder<T: DeserializeOwned>(path: &str, extension: &str) -> Vec<T> {
+    let entries = match fs::read_dir(path) {
+        Ok(entries) => entries,
+        Err(err) => {
+            warn!("Failed to read {}: {}", path, err);
+            return Vec::new();
+        }
+    };
+
+    entries
+        .flatten()
+        .filter_map(|entry| entry.path().to_str().map(String::from))
+        .filter(|path| path.ends_with(extension))
+        .filter_map(|path| load_ron_file(&path))
+        .collect()
+}
+
+/// A file in the temp folder for a test to write to. Every test passes its own `name`, as the
+/// tests run in parallel.
+#[cfg(test)]
+pub fn temp_path(name: &str) -> String {
+    std::env::temp_dir()
+        .join(format!("escape_pod_{}_{}.ron", name, std::process::id()))
+        .to_string_lossy()
+        .into_owned()
+}
@@ -0,0 +1,3 @@
+// File
+pub const SAVE_PATH: &str = "save.ron";
+pub const SAVE_VERSION: u32 = 8;
@@ -0,0 +1,11 @@
+mod constants;
+mod plugin;
+mod resources;
+mod systems;
+
+pub use self::{
+    constants::SAVE_PATH,
+    plugin::{SavePlugin, SaveSystem},
+    resources::{RunToRestore, SavedRun},
+    systems::RunSnapshot,
+};
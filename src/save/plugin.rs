@@ -0,0 +1,18 @@
+use super::{systems::restore_run_system, RunToRestore};
+use crate::schedule::GameState;
+use bevy::prelude::*;
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash, SystemLabel)]
+pub struct SaveSystem;
+
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RunToRestore>().add_system_set(
+            SystemSet::on_update(GameState::BeforeInGame)
+                .label(SaveSystem)
+                .with_system(restore_run_system),
+        );
+    }
+}
@@ -0,0 +1,219 @@
+use super::constants::SAVE_VERSION;
+use crate::{
+    components::{Bullet, Cannon, Health, Properties, ShieldForcefield, Velocity, Zapper},
+    faction::Faction,
+    ron_file::{load_ron_file, save_ron_file},
+    stats::Stats,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SavedTransform {
+    pub translation: [f32; 3],
+    pub rotation: [f32; 4],
+    pub scale: [f32; 3],
+}
+
+impl From<&Transform> for SavedTransform {
+    fn from(transform: &Transform) -> Self {
+        Self {
+            translation: transform.translation.into(),
+            rotation: transform.rotation.to_array(),
+            scale: transform.scale.into(),
+        }
+    }
+}
+
+impl From<&SavedTransform> for Transform {
+    fn from(transform: &SavedTransform) -> Self {
+        Self {
+            translation: Vec3::from(transform.translation),
+            rotation: Quat::from_array(transform.rotation),
+            scale: Vec3::from(transform.scale),
+        }
+    }
+}
+
+/// The definition id of a node together with its current stats.
+#[derive(Clone, Serialize, Deserialize)]
+pub enum SavedModule {
+    Core,
+    Debris(String),
+    Zapper(String, Zapper),
+    Cannon(String, Cannon),
+    Shield(String, ShieldForcefield),
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SavedNode {
+    pub transform: SavedTransform,
+    pub module: SavedModule,
+    pub health: Option<Health>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SavedPlayer {
+    pub transform: SavedTransform,
+    pub dist: f32,
+    pub properties: Properties,
+    pub nodes: Vec<SavedNode>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SavedEnemy {
+    pub transform: SavedTransform,
+    pub blueprint: String,
+    pub faction: Faction,
+    pub properties: Properties,
+    // Health of the core, which is the root of the ship
+    pub health: Health,
+    pub nodes: Vec<SavedNode>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SavedObject {
+    pub node: SavedNode,
+    pub velocity: Option<Velocity>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SavedProjectile {
+    pub transform: SavedTransform,
+    pub velocity: Velocity,
+    pub bullet: Bullet,
+    pub faction: Faction,
+}
+
+/// Everything needed to continue a run later on.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SavedRun {
+    pub version: u32,
+    pub stats: Stats,
+    pub player: SavedPlayer,
+    pub enemies: Vec<SavedEnemy>,
+    pub objects: Vec<SavedObject>,
+    pub projectiles: Vec<SavedProjectile>,
+}
+
+impl SavedRun {
+    pub fn load(path: &str) -> Option<Self> {
+        let run: Self = load_ron_file(path)?;
+
+        if run.version != SAVE_VERSION {
+            warn!(
+                "Save {} has version {}, expected {}",
+                path, run.version, SAVE_VERSION
+            );
+            return None;
+        }
+
+        Some(run)
+    }
+
+    pub fn save(&self, path: &str) {
+        save_ron_file(self, path);
+    }
+
+    pub fn delete(path: &str) {
+        if let Err(err) = fs::remove_file(path) {
+            warn!("Failed to delete {}: {}", path, err);
+        }
+    }
+}
+
+/// A saved run that replaces the new run once it has been spawned.
+#[derive(Default)]
+pub struct RunToRestore(pub Option<SavedRun>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ron_file::temp_path;
+
+    fn saved_run() -> SavedRun {
+        let transform = SavedTransform::from(&Transform::from_xyz(12., -3., 0.));
+        SavedRun {
+            version: SAVE_VERSION,
+            stats: Stats {
+                score: 42,
+                kills: 3,
+                enemies_alive: 1,
+                ..default()
+            },
+            player: SavedPlayer {
+                transform,
+                dist: 16.,
+                properties: Properties { size: 2, health: 7 },
+                nodes: vec![
+                    SavedNode {
+                        transform,
+                        module: SavedModule::Core,
+                        health: None,
+                    },
+                    SavedNode {
+                        transform,
+                        module: SavedModule::Zapper(
+                            "zapper".to_string(),
+                            Zapper {
+                                damage: 2,
+                                fire_rate: 0.5,
+                                cooldown_timer: 0.25,
+                                range: 80.,
+                            },
+                        ),
+                        health: Some(Health { current: 1, max: 3 }),
+                    },
+                ],
+            },
+            enemies: vec![SavedEnemy {
+                transform,
+                blueprint: "scout".to_string(),
+                faction: Faction::Drones,
+                properties: Properties { size: 1, health: 5 },
+                health: Health { current: 5, max: 5 },
+                nodes: Vec::new(),
+            }],
+            objects: Vec::new(),
+            projectiles: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn saved_runs_load_as_they_were_saved() {
+        let path = temp_path("save_round_trip");
+        saved_run().save(&path);
+        let run = SavedRun::load(&path).unwrap();
+        SavedRun::delete(&path);
+
+        assert_eq!(run.stats.score, 42);
+        assert_eq!(run.stats.kills, 3);
+        assert_eq!(run.player.transform.translation, [12., -3., 0.]);
+        assert_eq!(run.player.nodes.len(), 2);
+        match &run.player.nodes[1].module {
+            SavedModule::Zapper(id, stats) => {
+                assert_eq!(id, "zapper");
+                assert_eq!(stats.cooldown_timer, 0.25);
+            }
+            _ => panic!("The zapper was not loaded as one"),
+        }
+        assert_eq!(run.player.nodes[1].health.as_ref().unwrap().current, 1);
+        assert_eq!(run.enemies[0].blueprint, "scout");
+        assert_eq!(run.enemies[0].faction, Faction::Drones);
+    }
+
+    #[test]
+    fn saves_of_another_version_are_not_loaded() {
+        let path = temp_path("save_version");
+        SavedRun {
+            version: SAVE_VERSION + 1,
+            ..saved_run()
+        }
+        .save(&path);
+        let run = SavedRun::load(&path);
+        SavedRun::delete(&path);
+
+        assert!(run.is_none());
+    }
+}
@@ -0,0 +1,469 @@
+use super::{
+    constants::{SAVE_PATH, SAVE_VERSION},
+    resources::{
+        RunToRestore, SavedEnemy, SavedModule, SavedNode, SavedObject, SavedPlayer,
+        SavedProjectile, SavedRun,
+    },
+};
+use crate::{
+    asset::SpriteHandles,
+    components::{
+        Bullet, Cannon, Collider, Health, Projectile, Properties, Shield, ShieldForcefield,
+        Velocity, Zapper,
+    },
+    enemy::{Enemy, EnemyBlueprints, EnemyRoot},
+    faction::Faction,
+    grid::{GridCell, ShipGrid},
+    module::{Module, ModuleDefinitions},
+    nodes::{
+        spawn_cannon_node, spawn_debris_node, spawn_empty_node, spawn_shield_node_with_stats,
+        spawn_zapper_node,
+    },
+    object::{Object, BULLET_COLLIDER},
+    player::{Player, PlayerCore, PlayerRoot},
+    replay::ReplayMode,
+    stats::Stats,
+};
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+type ModuleQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static Transform,
+        Option<&'static Zapper>,
+        Option<&'static Cannon>,
+        Option<&'static Shield>,
+        Option<&'static PlayerCore>,
+        Option<&'static Module>,
+        Option<&'static Health>,
+        Option<&'static Children>,
+    ),
+>;
+
+fn save_node(
+    entity: Entity,
+    module_query: &ModuleQuery,
+    forcefield_query: &Query<&ShieldForcefield>,
+) -> Option<SavedNode> {
+    let (transform, zapper, cannon, shield, core, module, health, children) =
+        module_query.get(entity).ok()?;
+
+    let module = if core.is_some() {
+        SavedModule::Core
+    } else {
+        let id = module?.id.clone();
+        if let Some(zapper) = zapper {
+            SavedModule::Zapper(id, zapper.clone())
+        } else if let Some(cannon) = cannon {
+            SavedModule::Cannon(id, cannon.clone())
+        } else if shield.is_some() {
+            // The stats of a shield live on its forcefield
+            let forcefield = children?
+                .iter()
+                .find_map(|child| forcefield_query.get(*child).ok())?;
+            SavedModule::Shield(id, forcefield.clone())
+        } else {
+            SavedModule::Debris(id)
+        }
+    };
+
+    Some(SavedNode {
+        transform: transform.into(),
+        module,
+        health: health.cloned(),
+    })
+}
+
+/// Everything of the current run that goes into a save. Saving reads the ship as it is, so it
+/// has to happen before the run ends and the ship breaks apart.
+#[derive(SystemParam)]
+pub struct RunSnapshot<'w, 's> {
+    stats: Res<'w, Stats>,
+    player_query: Query<
+        'w,
+        's,
+        (
+            &'static Transform,
+            &'static PlayerRoot,
+            &'static Properties,
+            &'static Children,
+        ),
+    >,
+    enemy_query: Query<
+        'w,
+        's,
+        (
+            &'static Transform,
+            &'static EnemyRoot,
+            &'static Faction,
+            &'static Properties,
+            &'static Health,
+            &'static Children,
+        ),
+    >,
+    object_query:
+        Query<'w, 's, (Entity, Option<&'static Velocity>), (With<Object>, Without<Parent>)>,
+    projectile_query: Query<
+        'w,
+        's,
+        (
+            &'static Transform,
+            &'static Velocity,
+            &'static Bullet,
+            &'static Faction,
+        ),
+        With<Projectile>,
+    >,
+    module_query: ModuleQuery<'w, 's>,
+    forcefield_query: Query<'w, 's, &'static ShieldForcefield>,
+}
+
+impl<'w, 's> RunSnapshot<'w, 's> {
+    /// The run as it is right now, or `None` if there is no player to save.
+    pub fn take(&self) -> Option<SavedRun> {
+        let (player_transform, player_root, player_properties, player_children) =
+            match self.player_query.get_single() {
+                Ok(player) => player,
+                Err(_) => return None,
+            };
+
+        Some(SavedRun {
+            version: SAVE_VERSION,
+            stats: self.stats.clone(),
+            player: SavedPlayer {
+                transform: player_transform.into(),
+                dist: player_root.dist,
+                properties: player_properties.clone(),
+                nodes: player_children
+                    .iter()
+                    .filter_map(|child| {
+                        save_node(*child, &self.module_query, &self.forcefield_query)
+                    })
+                    .collect(),
+            },
+            enemies: self
+                .enemy_query
+                .iter()
+                .map(
+                    |(transform, root, faction, properties, health, children)| SavedEnemy {
+                        transform: transform.into(),
+                        blueprint: root.blueprint.clone(),
+                        faction: *faction,
+                        properties: properties.clone(),
+                        health: health.clone(),
+                        nodes: children
+                            .iter()
+                            .filter_map(|child| {
+                                save_node(*child, &self.module_query, &self.forcefield_query)
+                            })
+                            .collect(),
+                    },
+                )
+                .collect(),
+            objects: self
+                .object_query
+                .iter()
+                .filter_map(|(entity, velocity)| {
+                    Some(SavedObject {
+                        node: save_node(entity, &self.module_query, &self.forcefield_query)?,
+                        velocity: velocity.cloned(),
+                    })
+                })
+                .collect(),
+            projectiles: self
+                .projectile_query
+                .iter()
+                .map(|(transform, velocity, bullet, faction)| SavedProjectile {
+                    transform: transform.into(),
+                    velocity: velocity.clone(),
+                    bullet: bullet.clone(),
+                    faction: *faction,
+                })
+                .collect(),
+        })
+    }
+
+    pub fn save(&self, path: &str) {
+        if let Some(run) = self.take() {
+            run.save(path);
+        }
+    }
+}
+
+fn spawn_saved_node(
+    commands: &mut Commands,
+    sprite_handles: &SpriteHandles,
+    module_definitions: &ModuleDefinitions,
+    node: &SavedNode,
+    is_enemy: bool,
+) -> Option<Entity> {
+    // Modules whose definition is gone since the run was saved are left out
+    let entity = match &node.module {
+        SavedModule::Core => {
+            spawn_empty_node(commands, Vec3::ZERO, 0., sprite_handles.player.clone())
+        }
+        SavedModule::Debris(id) => {
+            spawn_debris_node(commands, module_definitions, id, Vec3::ZERO, 0.)?
+        }
+        SavedModule::Zapper(id, stats) => {
+            let zapper = spawn_zapper_node(commands, module_definitions, id, Vec3::ZERO, 0.)?;
+            commands.entity(zapper).insert(stats.clone()).id()
+        }
+        SavedModule::Cannon(id, stats) => {
+            let cannon = spawn_cannon_node(commands, module_definitions, id, Vec3::ZERO, 0.)?;
+            commands.entity(cannon).insert(stats.clone()).id()
+        }
+        SavedModule::Shield(id, stats) => spawn_shield_node_with_stats(
+            commands,
+            module_definitions,
+            id,
+            Vec3::ZERO,
+            0.,
+            stats.clone(),
+            is_enemy,
+        )?,
+    };
+
+    // Damaged nodes keep their damage
+    if let Some(health) = &node.health {
+        commands.entity(entity).insert(health.clone());
+    }
+
+    Some(
+        commands
+            .entity(entity)
+            .insert(Transform::from(&node.transform))
+            .id(),
+    )
+}
+
+fn spawn_saved_player(
+    commands: &mut Commands,
+    sprite_handles: &SpriteHandles,
+    module_definitions: &ModuleDefinitions,
+    player: &SavedPlayer,
+) {
+    let root = commands
+        .spawn()
+        .insert(Collider::default())
+        .insert(Player)
+        .insert(Faction::Player)
+        .insert(PlayerRoot { dist: player.dist })
+        .insert(Velocity::default())
+        .insert(player.properties.clone())
+        .insert_bundle(SpriteBundle {
+            transform: Transform::from(&player.transform),
+            texture: sprite_handles.player.clone(),
+            ..default()
+        })
+        .id();
+
+    // The nodes were saved on the grid, so their cells follow from their positions
+    let mut grid = ShipGrid::default();
+    for node in &player.nodes {
+        let entity =
+            match spawn_saved_node(commands, sprite_handles, module_definitions, node, false) {
+                Some(entity) => entity,
+                None => continue,
+            };
+        let cell = ShipGrid::cell_at(Transform::from(&node.transform).translation.truncate());
+        grid.occupy(cell, entity);
+        commands
+            .entity(entity)
+            .insert(Player)
+            .insert(Faction::Player)
+            .insert(GridCell(cell));
+        if let SavedModule::Core = node.module {
+            commands
+                .entity(entity)
+                .insert(Collider::default())
+                .insert(PlayerCore);
+        }
+        commands.entity(root).add_child(entity);
+    }
+    commands.entity(root).insert(grid);
+}
+
+fn spawn_saved_enemy(
+    commands: &mut Commands,
+    sprite_handles: &SpriteHandles,
+    module_definitions: &ModuleDefinitions,
+    enemy: &SavedEnemy,
+) {
+    let root = spawn_empty_node(commands, Vec3::ZERO, 0., sprite_handles.debris.clone());
+    commands
+        .entity(root)
+        .insert(Transform::from(&enemy.transform))
+        .insert(Collider::default())
+        .insert(Enemy)
+        .insert(enemy.faction)
+        .insert(Velocity::default())
+        .insert(EnemyRoot {
+            blueprint: enemy.blueprint.clone(),
+        })
+        .insert(enemy.properties.clone())
+        .insert(enemy.health.clone());
+
+    for node in &enemy.nodes {
+        let entity =
+            match spawn_saved_node(commands, sprite_handles, module_definitions, node, true) {
+                Some(entity) => entity,
+                None => continue,
+            };
+        commands.entity(entity).insert(Enemy).insert(enemy.faction);
+        commands.entity(root).add_child(entity);
+    }
+}
+
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn restore_run_system(
+    mut commands: Commands,
+    mut run_to_restore: ResMut<RunToRestore>,
+    sprite_handles: Res<SpriteHandles>,
+    module_definitions: Res<ModuleDefinitions>,
+    enemy_blueprints: Res<EnemyBlueprints>,
+    mut stats: ResMut<Stats>,
+    mut replay_mode: ResMut<ReplayMode>,
+    new_run_query: Query<Entity, Or<(With<PlayerRoot>, With<Object>)>>,
+) {
+    let run = match run_to_restore.0.take() {
+        Some(run) => run,
+        None => return,
+    };
+
+    // A save can only be continued once
+    SavedRun::delete(SAVE_PATH);
+
+    // Replace the freshly spawned run with the saved one
+    for entity in new_run_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    // The inputs of a continued run can't be played back from the start
+    *replay_mode = ReplayMode::Idle;
+    *stats = run.stats;
+
+    spawn_saved_player(
+        &mut commands,
+        &sprite_handles,
+        &module_definitions,
+        &run.player,
+    );
+
+    for enemy in &run.enemies {
+        // An enemy whose blueprint is gone since the run was saved is left out
+        if enemy_blueprints.get(&enemy.blueprint).is_none() {
+            warn!("Unknown enemy blueprint {}", enemy.blueprint);
+            stats.enemies_alive = stats.enemies_alive.saturating_sub(1);
+            continue;
+        }
+        spawn_saved_enemy(&mut commands, &sprite_handles, &module_definitions, enemy);
+    }
+
+    for object in &run.objects {
+        let entity = match spawn_saved_node(
+            &mut commands,
+            &sprite_handles,
+            &module_definitions,
+            &object.node,
+            false,
+        ) {
+            Some(entity) => entity,
+            None => continue,
+        };
+        commands.entity(entity).insert(Object);
+        if let Some(velocity) = &object.velocity {
+            commands.entity(entity).insert(velocity.clone());
+        }
+    }
+
+    for projectile in &run.projectiles {
+        commands
+            .spawn()
+            .insert_bundle(SpriteBundle {
+                transform: Transform::from(&projectile.transform),
+                sprite: Sprite {
+                    color: projectile.faction.color(),
+                    ..default()
+                },
+                ..default()
+            })
+            .insert(Projectile {})
+            .insert(BULLET_COLLIDER)
+            .insert(projectile.faction)
+            .insert(projectile.bullet.clone())
+            .insert(projectile.velocity.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        player::explode_player_system, random::GameRng, ron_file::temp_path, schedule::GameState,
+    };
+
+    struct TestSavePath(String);
+
+    // What the main menu button of the paused screen does
+    fn save_and_leave_system(
+        run_snapshot: RunSnapshot,
+        path: Res<TestSavePath>,
+        mut game_state: ResMut<State<GameState>>,
+    ) {
+        run_snapshot.save(&path.0);
+        game_state.set(GameState::AfterInGame).unwrap();
+    }
+
+    #[test]
+    fn runs_saved_from_the_paused_screen_keep_the_ship_together() {
+        let path = temp_path("save_paused");
+        let mut world = World::new();
+        world.insert_resource(State::new(GameState::Paused));
+        world.insert_resource(TestSavePath(path.clone()));
+        world.insert_resource(Stats::default());
+        world.insert_resource(GameRng::new(Some(1)));
+
+        let root_transform =
+            Transform::from_xyz(100., 50., 0.).with_rotation(Quat::from_rotation_z(1.));
+        let node_transform = Transform::from_xyz(8., 0., 0.);
+        let node = world
+            .spawn()
+            .insert(node_transform)
+            .insert(GlobalTransform::from(root_transform * node_transform))
+            .insert(Player)
+            .insert(PlayerCore)
+            .id();
+        world
+            .spawn()
+            .insert(root_transform)
+            .insert(GlobalTransform::from(root_transform))
+            .insert(PlayerRoot { dist: 8. })
+            .insert(Properties { size: 2, health: 1 })
+            .push_children(&[node]);
+
+        // Leaving the game explodes the ship within the same frame
+        let mut stage = SystemStage::single_threaded()
+            .with_system_set(State::<GameState>::get_driver())
+            .with_system_set(
+                SystemSet::on_update(GameState::Paused).with_system(save_and_leave_system),
+            )
+            .with_system_set(
+                SystemSet::on_enter(GameState::AfterInGame).with_system(explode_player_system),
+            );
+        stage.run(&mut world);
+        assert!(world.get::<Object>(node).is_some());
+
+        let run = SavedRun::load(&path).unwrap();
+        SavedRun::delete(&path);
+
+        // The node is saved where it sits on the ship, and only there
+        assert_eq!(run.player.nodes.len(), 1);
+        assert_eq!(
+            Transform::from(&run.player.nodes[0].transform).translation,
+            node_transform.translation
+        );
+        assert!(run.objects.is_empty());
+    }
+}
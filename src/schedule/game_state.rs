@@ -1,3 +1,5 @@
+use bevy::{ecs::schedule::ShouldRun, prelude::*};
+
 /// A state of the game.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum GameState {
@@ -20,3 +22,12 @@ pub enum GameState {
     EndScreen,
     AfterEndScreen,
 }
+
+/// Run criteria for what keeps going while the death of the player plays out, but
+/// freezes when paused.
+pub fn in_game_or_after(game_state: Res<State<GameState>>) -> ShouldRun {
+    match game_state.current() {
+        GameState::InGame | GameState::AfterInGame => ShouldRun::Yes,
+        _ => ShouldRun::No,
+    }
+}
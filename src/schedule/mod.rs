@@ -4,7 +4,7 @@ mod resources;
 mod systems;
 
 pub use self::{
-    game_state::GameState,
+    game_state::{in_game_or_after, GameState},
     plugin::{SchedulePlugin, ScheduleSystem},
     resources::{ScheduleQueue, ScheduleTimer},
 };
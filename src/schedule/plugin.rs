@@ -16,6 +16,7 @@ use super::{
     },
     GameState, ScheduleQueue,
 };
+use crate::player::PlayerInputSystem;
 use bevy::prelude::*;
 
 #[derive(Debug, PartialEq, Eq, Clone, Hash, SystemLabel)]
@@ -117,6 +118,7 @@ impl Plugin for SchedulePlugin {
             .add_system_set(
                 SystemSet::on_update(GameState::InGame)
                     .label(ScheduleSystem)
+                    .after(PlayerInputSystem)
                     .with_system(check_for_paused_system),
             )
             // Paused
@@ -1,12 +1,14 @@
-use crate::schedule::{resources::ScheduleQueue, GameState, ScheduleTimer};
+use crate::{
+    player::PlayerInput,
+    schedule::{resources::ScheduleQueue, GameState, ScheduleTimer},
+};
 use bevy::prelude::*;
 
 pub fn check_for_paused_system(
-    mut input: ResMut<Input<KeyCode>>,
+    player_input: Res<PlayerInput>,
     mut game_state: ResMut<State<GameState>>,
 ) {
-    if input.just_pressed(KeyCode::Escape) {
-        input.clear_just_pressed(KeyCode::Escape);
+    if player_input.pause {
         game_state.push(GameState::Paused).unwrap();
     }
 }
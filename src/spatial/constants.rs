@@ -0,0 +1,2 @@
+// Side length of a cell of the spatial index, a few nodes wide
+pub const SPATIAL_CELL_SIZE: f32 = 32.;
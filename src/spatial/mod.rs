@@ -0,0 +1,9 @@
+mod constants;
+mod plugin;
+mod resources;
+mod systems;
+
+pub use self::{
+    plugin::{SpatialPlugin, SpatialSystem},
+    resources::SpatialIndex,
+};
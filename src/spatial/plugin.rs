@@ -0,0 +1,28 @@
+use super::{constants::SPATIAL_CELL_SIZE, systems::rebuild_spatial_index_system, SpatialIndex};
+use crate::{
+    object::ObjectSystem,
+    physics::{on_physics_update, PhysicsStage, PhysicsSystem},
+    player::PlayerSystem,
+    schedule::GameState,
+};
+use bevy::prelude::*;
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash, SystemLabel)]
+pub struct SpatialSystem;
+
+pub struct SpatialPlugin;
+
+impl Plugin for SpatialPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(SpatialIndex::new(SPATIAL_CELL_SIZE))
+            .add_system_set_to_stage(
+                PhysicsStage,
+                on_physics_update(GameState::InGame)
+                    .label(SpatialSystem)
+                    .after(PhysicsSystem::Propagate)
+                    .before(ObjectSystem)
+                    .before(PlayerSystem)
+                    .with_system(rebuild_spatial_index_system),
+            );
+    }
+}
@@ -0,0 +1,149 @@
+use bevy::{prelude::*, utils::HashMap};
+
+/// A spatial hash of node positions in world space, rebuilt every physics step once the
+/// transforms of that step are propagated, so systems can look up the nodes around a point
+/// without going over every node in the world. Query it with world positions as well.
+pub struct SpatialIndex {
+    cell_size: f32,
+    cells: HashMap<IVec2, Vec<(Entity, Vec2)>>,
+    // Largest bounding radius of everything in the index
+    max_radius: f32,
+}
+
+impl SpatialIndex {
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size,
+            cells: HashMap::default(),
+            max_radius: 0.,
+        }
+    }
+
+    fn cell(&self, position: Vec2) -> IVec2 {
+        (position / self.cell_size).floor().as_ivec2()
+    }
+
+    /// Removes all entries along with their cells, so cells the ships have left behind don't
+    /// pile up over a run.
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.max_radius = 0.;
+    }
+
+    /// Adds an entity, `radius` being how far its collider reaches from `position`.
+    pub fn insert(&mut self, entity: Entity, position: Vec2, radius: f32) {
+        self.max_radius = self.max_radius.max(radius);
+        let cell = self.cell(position);
+        self.cells.entry(cell).or_default().push((entity, position));
+    }
+
+    /// All entities within `radius` of `center`, together with their position.
+    pub fn query(&self, center: Vec2, radius: f32) -> impl Iterator<Item = (Entity, Vec2)> + '_ {
+        let min = self.cell(center - Vec2::splat(radius));
+        let max = self.cell(center + Vec2::splat(radius));
+
+        (min.x..=max.x)
+            .flat_map(move |x| (min.y..=max.y).map(move |y| IVec2::new(x, y)))
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .copied()
+            .filter(move |(_, position)| position.distance(center) < radius)
+    }
+
+    /// All entities whose collider could reach into a circle of `radius` around `center`.
+    /// Only a broadphase, the colliders still have to be tested against each other.
+    pub fn query_colliders(
+        &self,
+        center: Vec2,
+        radius: f32,
+    ) -> impl Iterator<Item = (Entity, Vec2)> + '_ {
+        self.query(center, radius + self.max_radius)
+    }
+
+    /// The closest entity within `radius` of `center` that passes the filter.
+    pub fn nearest(
+        &self,
+        center: Vec2,
+        radius: f32,
+        mut filter: impl FnMut(Entity) -> bool,
+    ) -> Option<(Entity, Vec2)> {
+        self.query(center, radius)
+            .filter(|(entity, _)| filter(*entity))
+            .min_by(|(_, a), (_, b)| {
+                a.distance_squared(center)
+                    .total_cmp(&b.distance_squared(center))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entity(index: u32) -> Entity {
+        Entity::from_raw(index)
+    }
+
+    fn sorted(entries: impl Iterator<Item = (Entity, Vec2)>) -> Vec<Entity> {
+        let mut entities: Vec<Entity> = entries.map(|(entity, _)| entity).collect();
+        entities.sort();
+        entities
+    }
+
+    #[test]
+    fn query_finds_entities_across_cells() {
+        let mut index = SpatialIndex::new(10.);
+        index.insert(entity(0), Vec2::new(-1., -1.), 0.);
+        index.insert(entity(1), Vec2::new(12., 0.), 0.);
+        index.insert(entity(2), Vec2::new(30., 0.), 0.);
+
+        assert_eq!(
+            sorted(index.query(Vec2::new(5., 0.), 10.)),
+            vec![entity(0), entity(1)]
+        );
+        assert_eq!(sorted(index.query(Vec2::new(5., 0.), 5.)), vec![]);
+    }
+
+    #[test]
+    fn query_colliders_reaches_as_far_as_the_largest_collider() {
+        let mut index = SpatialIndex::new(10.);
+        index.insert(entity(0), Vec2::new(20., 0.), 1.);
+        index.insert(entity(1), Vec2::new(-20., 0.), 12.);
+
+        // The search grows by the largest collider, so the small one is found too
+        assert_eq!(
+            sorted(index.query_colliders(Vec2::ZERO, 10.)),
+            vec![entity(0), entity(1)]
+        );
+        assert_eq!(sorted(index.query(Vec2::ZERO, 10.)), vec![]);
+    }
+
+    #[test]
+    fn clear_empties_the_index() {
+        let mut index = SpatialIndex::new(10.);
+        index.insert(entity(0), Vec2::ZERO, 50.);
+        index.clear();
+        assert!(index.cells.is_empty());
+        index.insert(entity(1), Vec2::new(30., 0.), 0.);
+
+        assert_eq!(sorted(index.query_colliders(Vec2::ZERO, 10.)), vec![]);
+    }
+
+    #[test]
+    fn nearest_picks_the_closest_entity_that_passes_the_filter() {
+        let mut index = SpatialIndex::new(10.);
+        index.insert(entity(0), Vec2::new(3., 0.), 0.);
+        index.insert(entity(1), Vec2::new(0., -6.), 0.);
+        index.insert(entity(2), Vec2::new(-25., 0.), 0.);
+
+        assert_eq!(
+            index.nearest(Vec2::ZERO, 30., |_| true),
+            Some((entity(0), Vec2::new(3., 0.)))
+        );
+        assert_eq!(
+            index.nearest(Vec2::ZERO, 30., |other| other.id() != 0),
+            Some((entity(1), Vec2::new(0., -6.)))
+        );
+        assert_eq!(index.nearest(Vec2::ZERO, 5., |other| other.id() != 0), None);
+    }
+}
@@ -0,0 +1,29 @@
+use super::SpatialIndex;
+use crate::{
+    components::{Collider, ShieldForcefield},
+    enemy::Enemy,
+    object::Object,
+    player::Player,
+};
+use bevy::prelude::*;
+
+#[allow(clippy::type_complexity)]
+pub fn rebuild_spatial_index_system(
+    mut spatial_index: ResMut<SpatialIndex>,
+    node_query: Query<
+        (Entity, &GlobalTransform, Option<&Collider>),
+        Or<(
+            With<Player>,
+            With<Enemy>,
+            With<Object>,
+            With<ShieldForcefield>,
+        )>,
+    >,
+) {
+    spatial_index.clear();
+    for (entity, global_transform, collider) in node_query.iter() {
+        let transform = global_transform.compute_transform();
+        let radius = collider.map_or(0., |collider| collider.bounding_radius(transform.scale));
+        spatial_index.insert(entity, transform.translation.truncate(), radius);
+    }
+}
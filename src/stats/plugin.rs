@@ -1,9 +1,14 @@
 use super::{
     resources::StatsTimer,
-    systems::{reset_stats_system, update_stats_system},
+    systems::{reset_stats_system, update_damage_stats_system, update_stats_system},
     Stats,
 };
-use crate::schedule::GameState;
+use crate::{
+    enemy::EnemySystem,
+    physics::{on_physics_update, PhysicsStage, PhysicsSystem},
+    player::PlayerSystem,
+    schedule::GameState,
+};
 use bevy::prelude::*;
 
 #[derive(Debug, PartialEq, Eq, Clone, Hash, SystemLabel)]
@@ -15,10 +20,16 @@ impl Plugin for StatsPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(Stats::default())
             .insert_resource(StatsTimer(Timer::from_seconds(3.0, true)))
-            .add_system_set(
-                SystemSet::on_update(GameState::InGame)
+            // The spawning of enemies depends on the stats, so they are counted every step
+            .add_system_set_to_stage(
+                PhysicsStage,
+                on_physics_update(GameState::InGame)
                     .label(StatsSystem)
-                    .with_system(update_stats_system),
+                    .after(PhysicsSystem::Propagate)
+                    .after(PlayerSystem)
+                    .after(EnemySystem)
+                    .with_system(update_stats_system)
+                    .with_system(update_damage_stats_system),
             )
             .add_system_set(
                 SystemSet::on_enter(GameState::BeforeInGame)
@@ -1,10 +1,18 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Default)]
+#[derive(Default, Clone, Serialize, Deserialize)]
 pub struct Stats {
     pub score: u32,
     pub kills: u32,
     pub enemies_alive: u32,
+    // Damage of the hits landed by the player's weapons and on the player's ship
+    #[serde(default)]
+    pub zapper_damage_dealt: u32,
+    #[serde(default)]
+    pub cannon_damage_dealt: u32,
+    #[serde(default)]
+    pub damage_taken: u32,
 }
 
 impl Stats {
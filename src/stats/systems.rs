@@ -1,12 +1,14 @@
 use super::{resources::StatsTimer, Stats};
 use crate::{
     enemy::{EnemyKilled, EnemySpawned},
+    events::{DamageApplied, WeaponKind},
+    faction::Faction,
+    physics::PHYSICS_TIMESTEP,
     player::PlayerSizeIncreased,
 };
-use bevy::prelude::*;
+use bevy::{prelude::*, utils::Duration};
 
 pub fn update_stats_system(
-    time: Res<Time>,
     mut stats: ResMut<Stats>,
     mut score_timer: ResMut<StatsTimer>,
 
@@ -14,7 +16,11 @@ pub fn update_stats_system(
     enemy_spawned_events: EventReader<EnemySpawned>,
     player_size_increased_events: EventReader<PlayerSizeIncreased>,
 ) {
-    if score_timer.0.tick(time.delta()).just_finished() {
+    if score_timer
+        .0
+        .tick(Duration::from_secs_f32(PHYSICS_TIMESTEP))
+        .just_finished()
+    {
         stats.score += 10;
     }
 
@@ -42,6 +48,31 @@ pub fn update_stats_system(
     }
 }
 
+// Attributes hits to the player by the faction of the weapon and of the node that got hit
+pub fn update_damage_stats_system(
+    mut stats: ResMut<Stats>,
+    mut damage_applied_events: EventReader<DamageApplied>,
+    faction_query: Query<&Faction>,
+) {
+    let is_player = |entity: Option<Entity>| {
+        entity
+            .and_then(|entity| faction_query.get(entity).ok())
+            .map_or(false, |faction| *faction == Faction::Player)
+    };
+
+    for hit in damage_applied_events.iter() {
+        if is_player(hit.source) {
+            match hit.weapon {
+                WeaponKind::Zapper => stats.zapper_damage_dealt += hit.damage,
+                WeaponKind::Cannon => stats.cannon_damage_dealt += hit.damage,
+            }
+        }
+        if is_player(Some(hit.target)) {
+            stats.damage_taken += hit.damage;
+        }
+    }
+}
+
 pub fn reset_stats_system(mut stats: ResMut<Stats>, mut timer: ResMut<StatsTimer>) {
     timer.0.reset();
     stats.reset();
@@ -30,13 +30,49 @@ pub struct UiKills;
 #[derive(Component)]
 pub struct UiVolume;
 
+#[derive(Component)]
+pub struct UiShake;
+
+#[derive(Component)]
+pub struct UiDamageNumbers;
+
 #[derive(Component)]
 pub struct UiEnemiesAlive;
 
+#[derive(Component)]
+pub struct UiRadar;
+
+#[derive(Component)]
+pub struct UiRadarBlip;
+
+#[derive(Component)]
+pub struct OffscreenIndicator;
+
+// Floats above an enemy ship and shows up for a while after it took damage
+#[derive(Component)]
+pub struct HealthBar {
+    pub ship: Entity,
+    // The most health the ship's nodes have added up to
+    pub max_health: u32,
+    // The health of the ship when the bar was last updated
+    pub last_health: u32,
+    // Height above the ship, so the bar clears its modules
+    pub offset: f32,
+    // Seconds until the bar is hidden again
+    pub timer: f32,
+}
+
+#[derive(Component)]
+pub struct HealthBarFill;
+
 #[derive(Component)]
 pub enum MainMenuButtonAction {
+    Continue,
     Play,
+    Replay,
     Volume,
+    Shake,
+    DamageNumbers,
     Quit,
 }
 
@@ -44,6 +80,8 @@ pub enum MainMenuButtonAction {
 pub enum PausedScreenButtonAction {
     Continue,
     Volume,
+    Shake,
+    DamageNumbers,
     MainMenu,
     Quit,
 }
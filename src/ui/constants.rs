@@ -1,4 +1,6 @@
-use crate::colors::{COLOR_ACCENT, COLOR_BACKGROUND_LIGHT, COLOR_BACKGROUND_MEDIUM};
+use crate::colors::{
+    COLOR_ACCENT, COLOR_BACKGROUND_LIGHT, COLOR_BACKGROUND_MEDIUM, COLOR_FOREGROUND,
+};
 use bevy::prelude::Color;
 
 // Colors
@@ -7,8 +9,40 @@ pub const COLOR_BUTTON_DEFAULT: Color = COLOR_BACKGROUND_MEDIUM;
 pub const COLOR_BUTTON_HOVERED: Color = COLOR_BACKGROUND_LIGHT;
 pub const COLOR_BUTTON_CLICKED: Color = COLOR_ACCENT;
 
+// Radar, loose modules are colored by their kind
+pub const COLOR_RADAR_BACKGROUND: Color =
+    Color::rgba(20.0 / 255.0, 17.0 / 255.0, 18.0 / 255.0, 0.6);
+pub const COLOR_RADAR_DEBRIS: Color = COLOR_BACKGROUND_LIGHT;
+pub const COLOR_RADAR_ZAPPER: Color = Color::rgb(170.0 / 255.0, 120.0 / 255.0, 255.0 / 255.0);
+pub const COLOR_RADAR_CANNON: Color = COLOR_FOREGROUND;
+pub const COLOR_RADAR_SHIELD: Color = Color::rgb(90.0 / 255.0, 220.0 / 255.0, 255.0 / 255.0);
+
 // Font sizes
 pub const FONT_SIZE_HUGE: f32 = 180.0;
 pub const FONT_SIZE_LARGE: f32 = 120.0;
 pub const FONT_SIZE_MEDIUM: f32 = 100.0;
 pub const FONT_SIZE_SMALL: f32 = 60.0;
+
+// Radar sizes in pixels
+pub const RADAR_SIZE: f32 = 180.0;
+pub const RADAR_BLIP_SIZE: f32 = 6.0;
+// Zooms the radar cycles through, below 1 it shows more than its range
+pub const RADAR_ZOOM_LEVELS: [f32; 3] = [1.0, 2.0, 0.5];
+
+// Off-screen indicators, sizes and the margin to the screen edge in pixels
+pub const INDICATOR_MARGIN: f32 = 20.0;
+pub const INDICATOR_MIN_SIZE: f32 = 8.0;
+pub const INDICATOR_MAX_SIZE: f32 = 24.0;
+// Things this far from the player in units, or further, get the smallest arrows
+pub const INDICATOR_FAR_DISTANCE: f32 = 1500.0;
+// Above everything else in the world, but still in front of the camera
+pub const INDICATOR_Z: f32 = 0.95;
+
+// Health bars above enemy ships, sizes in units
+pub const HEALTH_BAR_WIDTH: f32 = 16.0;
+pub const HEALTH_BAR_HEIGHT: f32 = 2.0;
+pub const HEALTH_BAR_MARGIN: f32 = 8.0;
+// Seconds a bar stays up after the last damage
+pub const HEALTH_BAR_SHOW_TIME: f32 = 2.0;
+// Below the off-screen indicators
+pub const HEALTH_BAR_Z: f32 = 0.9;
@@ -96,3 +96,8 @@ pub fn default_button_bundle() -> ButtonBundle {
         ..default()
     }
 }
+
+// Text for a setting that is either on or off
+pub fn on_off_label(enabled: bool) -> String {
+    if enabled { "On" } else { "Off" }.to_string()
+}
@@ -2,6 +2,7 @@ mod components;
 mod constants;
 mod helper;
 mod plugin;
+mod resources;
 mod systems;
 
 pub use self::plugin::UiPlugin;
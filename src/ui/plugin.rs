@@ -1,15 +1,22 @@
 use super::{
     components::{OnDeathScreen, OnIngameScreen, OnMainMenuScreen, OnPausedScreen, OnSplashScreen},
+    resources::RadarSettings,
     systems::{
         button_highlight_system, end_screen_button_interaction_system,
         main_menu_button_interaction_system, paused_button_interaction_system,
-        spawn_end_screen_ui_system, spawn_ingame_ui_system, spawn_main_menu_ui_system,
-        spawn_paused_ui_system, spawn_splash_screen_system, update_splash_screen_system,
-        update_ui_enemies_alive_system, update_ui_kills_system, update_ui_player_stats_system,
-        update_ui_score_system, update_ui_volume_system,
+        read_radar_zoom_input_system, spawn_end_screen_ui_system, spawn_health_bars_system,
+        spawn_ingame_ui_system, spawn_main_menu_ui_system, spawn_paused_ui_system,
+        spawn_radar_ui_system, spawn_splash_screen_system, update_health_bars_system,
+        update_offscreen_indicators_system, update_splash_screen_system,
+        update_ui_damage_numbers_system, update_ui_enemies_alive_system, update_ui_kills_system,
+        update_ui_player_stats_system, update_ui_radar_system, update_ui_score_system,
+        update_ui_shake_system, update_ui_volume_system,
     },
 };
-use crate::{despawn_recursive::despawn_entities_recursive_system, schedule::GameState};
+use crate::{
+    camera::CameraSystem, despawn_recursive::despawn_entities_recursive_system,
+    player::PlayerSystem, schedule::GameState,
+};
 use bevy::prelude::*;
 
 #[derive(Debug, PartialEq, Eq, Clone, Hash, SystemLabel)]
@@ -20,7 +27,8 @@ pub struct UiPlugin;
 
 impl Plugin for UiPlugin {
     fn build(&self, app: &mut App) {
-        app.add_system(button_highlight_system)
+        app.init_resource::<RadarSettings>()
+            .add_system(button_highlight_system)
             // Splash Screen
             .add_system_set(
                 SystemSet::on_enter(GameState::BeforeSplashScreen)
@@ -47,7 +55,9 @@ impl Plugin for UiPlugin {
                 SystemSet::on_update(GameState::MainMenu)
                     .label(UiSystem)
                     .with_system(main_menu_button_interaction_system)
-                    .with_system(update_ui_volume_system),
+                    .with_system(update_ui_volume_system)
+                    .with_system(update_ui_shake_system)
+                    .with_system(update_ui_damage_numbers_system),
             )
             .add_system_set(
                 SystemSet::on_exit(GameState::AfterMainMenu)
@@ -58,7 +68,8 @@ impl Plugin for UiPlugin {
             .add_system_set(
                 SystemSet::on_enter(GameState::BeforeInGame)
                     .label(UiSystem)
-                    .with_system(spawn_ingame_ui_system),
+                    .with_system(spawn_ingame_ui_system)
+                    .with_system(spawn_radar_ui_system),
             )
             .add_system_set(
                 SystemSet::on_update(GameState::BeforeInGame)
@@ -71,6 +82,17 @@ impl Plugin for UiPlugin {
             .add_system_set(
                 SystemSet::on_update(GameState::InGame)
                     .label(UiSystem)
+                    .with_system(read_radar_zoom_input_system.before(update_ui_radar_system))
+                    .with_system(update_ui_radar_system)
+                    // Once the camera has moved for this frame
+                    .with_system(update_offscreen_indicators_system.after(CameraSystem))
+                    // Once the health of the ships is up to date
+                    .with_system(
+                        spawn_health_bars_system
+                            .after(PlayerSystem)
+                            .before(update_health_bars_system),
+                    )
+                    .with_system(update_health_bars_system.after(PlayerSystem))
                     .with_system(update_ui_score_system)
                     .with_system(update_ui_player_stats_system)
                     .with_system(update_ui_kills_system)
@@ -91,7 +113,9 @@ impl Plugin for UiPlugin {
                 SystemSet::on_update(GameState::Paused)
                     .label(UiSystem)
                     .with_system(paused_button_interaction_system)
-                    .with_system(update_ui_volume_system),
+                    .with_system(update_ui_volume_system)
+                    .with_system(update_ui_shake_system)
+                    .with_system(update_ui_damage_numbers_system),
             )
             .add_system_set(
                 SystemSet::on_exit(GameState::Paused)
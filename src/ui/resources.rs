@@ -0,0 +1,29 @@
+use super::constants::RADAR_ZOOM_LEVELS;
+
+/// What the radar in the corner of the screen covers.
+pub struct RadarSettings {
+    // Distance from the player to the edge of the radar in units, at a zoom of 1
+    pub range: f32,
+    // Index into the zoom levels
+    zoom_level: usize,
+}
+
+impl Default for RadarSettings {
+    fn default() -> Self {
+        Self {
+            range: 600.,
+            zoom_level: 0,
+        }
+    }
+}
+
+impl RadarSettings {
+    pub fn cycle_zoom(&mut self) {
+        self.zoom_level = (self.zoom_level + 1) % RADAR_ZOOM_LEVELS.len();
+    }
+
+    // The distance that is actually shown
+    pub fn visible_range(&self) -> f32 {
+        self.range / RADAR_ZOOM_LEVELS[self.zoom_level]
+    }
+}
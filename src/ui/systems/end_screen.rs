@@ -1,6 +1,7 @@
 use crate::{
     asset::FontHandles,
     colors::COLOR_TRANSPARENT,
+    random::GameRng,
     schedule::{GameState, ScheduleQueue},
     stats::Stats,
     ui::{
@@ -16,6 +17,7 @@ use bevy::{app::AppExit, prelude::*};
 pub fn spawn_end_screen_ui_system(
     mut commands: Commands,
     stats: Res<Stats>,
+    rng: Res<GameRng>,
     font_handles: Res<FontHandles>,
 ) {
     commands
@@ -64,6 +66,18 @@ pub fn spawn_end_screen_ui_system(
                     format!("Kills: {}", stats.kills),
                     accent_medium_button_text_style(font_handles.default.clone()),
                 )
+                .with_style(Style {
+                    align_self: AlignSelf::Center,
+                    ..default()
+                })
+                .with_text_alignment(TextAlignment::CENTER),
+            );
+
+            parent.spawn_bundle(
+                TextBundle::from_section(
+                    format!("Seed: {}", rng.seed()),
+                    default_small_button_text_style(font_handles.default.clone()),
+                )
                 .with_style(Style {
                     align_self: AlignSelf::Center,
                     margin: UiRect::new(
@@ -0,0 +1,195 @@
+use crate::{
+    colors::COLOR_BACKGROUND_DARK,
+    components::{Health, Properties},
+    enemy::EnemyRoot,
+    faction::Faction,
+    ui::{
+        components::{HealthBar, HealthBarFill, OnIngameScreen},
+        constants::{
+            HEALTH_BAR_HEIGHT, HEALTH_BAR_MARGIN, HEALTH_BAR_SHOW_TIME, HEALTH_BAR_WIDTH,
+            HEALTH_BAR_Z,
+        },
+    },
+};
+use bevy::prelude::*;
+
+// Gives every new enemy ship a bar, whether it was spawned or restored from a save
+pub fn spawn_health_bars_system(
+    mut commands: Commands,
+    ship_query: Query<(Entity, &Transform, &Health, &Faction, Option<&Children>), Added<EnemyRoot>>,
+    node_query: Query<(&Transform, &Health)>,
+) {
+    for (ship, transform, core_health, faction, children) in ship_query.iter() {
+        let nodes: Vec<(&Transform, &Health)> = children
+            .iter()
+            .flat_map(|children| children.iter())
+            .filter_map(|child| node_query.get(*child).ok())
+            .collect();
+        let max_health = core_health.max + nodes.iter().map(|(_, health)| health.max).sum::<u32>();
+        let health =
+            core_health.current + nodes.iter().map(|(_, health)| health.current).sum::<u32>();
+        // The module furthest from the core decides how high the bar floats
+        let offset = nodes
+            .iter()
+            .map(|(node_transform, _)| node_transform.translation.truncate().length())
+            .fold(0., f32::max)
+            + HEALTH_BAR_MARGIN;
+
+        commands
+            .spawn_bundle(SpriteBundle {
+                transform: Transform::from_translation(
+                    (transform.translation.truncate() + Vec2::Y * offset).extend(HEALTH_BAR_Z),
+                ),
+                sprite: Sprite {
+                    color: COLOR_BACKGROUND_DARK,
+                    custom_size: Some(Vec2::new(HEALTH_BAR_WIDTH, HEALTH_BAR_HEIGHT)),
+                    ..default()
+                },
+                visibility: Visibility { is_visible: false },
+                ..default()
+            })
+            .insert(HealthBar {
+                ship,
+                max_health,
+                last_health: health,
+                offset,
+                timer: 0.,
+            })
+            .insert(OnIngameScreen)
+            .with_children(|parent| {
+                parent
+                    .spawn_bundle(SpriteBundle {
+                        transform: Transform::from_xyz(0., 0., 0.01),
+                        sprite: Sprite {
+                            color: faction.color(),
+                            custom_size: Some(Vec2::new(HEALTH_BAR_WIDTH, HEALTH_BAR_HEIGHT)),
+                            ..default()
+                        },
+                        visibility: Visibility { is_visible: false },
+                        ..default()
+                    })
+                    .insert(HealthBarFill);
+            });
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn update_health_bars_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    ship_query: Query<
+        (&Transform, &Properties, &Health, Option<&Children>),
+        (Without<HealthBar>, Without<HealthBarFill>),
+    >,
+    node_query: Query<&Health>,
+    mut bar_query: Query<
+        (
+            Entity,
+            &mut HealthBar,
+            &mut Transform,
+            &mut Visibility,
+            &Children,
+        ),
+        Without<HealthBarFill>,
+    >,
+    mut fill_query: Query<(&mut Transform, &mut Sprite, &mut Visibility), With<HealthBarFill>>,
+) {
+    for (bar_entity, mut bar, mut bar_transform, mut bar_visibility, children) in
+        bar_query.iter_mut()
+    {
+        // The ship is gone, so is its bar
+        let (ship_transform, properties, core_health, ship_children) =
+            match ship_query.get(bar.ship) {
+                Ok(ship) => ship,
+                Err(_) => {
+                    commands.entity(bar_entity).despawn_recursive();
+                    continue;
+                }
+            };
+
+        // Summed from the same nodes as the ship's health, which a restored save may only attach
+        // after the bar was spawned. Lost nodes still count, so the bar shows what was shot off.
+        let max_health = core_health.max
+            + ship_children
+                .iter()
+                .flat_map(|children| children.iter())
+                .filter_map(|child| node_query.get(*child).ok())
+                .map(|health| health.max)
+                .sum::<u32>();
+        bar.max_health = bar.max_health.max(max_health);
+
+        // Ships don't heal, any change is damage
+        if properties.health < bar.last_health {
+            bar.timer = HEALTH_BAR_SHOW_TIME;
+        }
+        bar.last_health = properties.health;
+        bar.timer = (bar.timer - time.delta_seconds()).max(0.);
+
+        // The bar doesn't turn with the ship
+        bar_transform.translation =
+            (ship_transform.translation.truncate() + Vec2::Y * bar.offset).extend(HEALTH_BAR_Z);
+        let is_visible = bar.timer > 0. && properties.health < bar.max_health;
+        bar_visibility.is_visible = is_visible;
+
+        // Fill from the left
+        let fraction = properties.health as f32 / bar.max_health.max(1) as f32;
+        for child in children.iter() {
+            if let Ok((mut fill_transform, mut fill_sprite, mut fill_visibility)) =
+                fill_query.get_mut(*child)
+            {
+                fill_sprite.custom_size =
+                    Some(Vec2::new(HEALTH_BAR_WIDTH * fraction, HEALTH_BAR_HEIGHT));
+                fill_transform.translation.x = -HEALTH_BAR_WIDTH * (1. - fraction) / 2.;
+                fill_visibility.is_visible = is_visible;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nodes_attached_after_the_bar_count_towards_its_max_health() {
+        let mut world = World::new();
+        world.insert_resource(Time::default());
+        let ship = world
+            .spawn()
+            .insert(Transform::default())
+            .insert(Health {
+                current: 10,
+                max: 10,
+            })
+            .insert(Properties {
+                size: 1,
+                health: 10,
+            })
+            .insert(Faction::Pirates)
+            .insert(EnemyRoot {
+                blueprint: "shieldy".to_string(),
+            })
+            .id();
+        let mut stage = SystemStage::single_threaded();
+        stage.add_system(spawn_health_bars_system.before(update_health_bars_system));
+        stage.add_system(update_health_bars_system);
+        stage.run(&mut world);
+
+        // Like a restored save, which spawns the nodes of a ship a frame later
+        let node = world
+            .spawn()
+            .insert(Transform::from_xyz(8., 0., 0.))
+            .insert(Health {
+                current: 20,
+                max: 20,
+            })
+            .id();
+        world.entity_mut(ship).push_children(&[node]);
+        world.get_mut::<Properties>(ship).unwrap().health = 30;
+        stage.run(&mut world);
+
+        let bar = world.query::<&HealthBar>().single(&world);
+        assert_eq!(bar.max_health, 30);
+        assert_eq!(bar.last_health, 30);
+    }
+}
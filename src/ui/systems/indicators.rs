@@ -0,0 +1,123 @@
+use crate::{
+    asset::SpriteHandles,
+    camera::MainCamera,
+    colors::{COLOR_ENEMY, COLOR_PLAYER},
+    enemy::EnemyRoot,
+    module::{Module, ModuleDefinitions, ModuleKind},
+    object::Object,
+    player::PlayerRoot,
+    ui::{
+        components::{OffscreenIndicator, OnIngameScreen},
+        constants::{
+            INDICATOR_FAR_DISTANCE, INDICATOR_MARGIN, INDICATOR_MAX_SIZE, INDICATOR_MIN_SIZE,
+            INDICATOR_Z,
+        },
+    },
+};
+use bevy::prelude::*;
+
+// Arrows at the edge of the screen, pointing at enemies and useful modules out of view.
+// They live in the world, so they can turn, but keep the same size on screen.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn update_offscreen_indicators_system(
+    mut commands: Commands,
+    sprite_handles: Res<SpriteHandles>,
+    module_definitions: Res<ModuleDefinitions>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    player_query: Query<&Transform, With<PlayerRoot>>,
+    enemy_query: Query<&Transform, With<EnemyRoot>>,
+    object_query: Query<(&Transform, &Module), (With<Object>, Without<Parent>)>,
+    mut indicator_query: Query<
+        (&mut Transform, &mut Sprite, &mut Visibility),
+        (
+            With<OffscreenIndicator>,
+            Without<PlayerRoot>,
+            Without<EnemyRoot>,
+            Without<Object>,
+        ),
+    >,
+) {
+    let (camera, camera_transform) = camera_query.single();
+    let viewport_size = match camera.logical_viewport_size() {
+        Some(size) => size,
+        None => return,
+    };
+    let player_position = player_query.single().translation.truncate();
+    let viewport_to_world =
+        camera_transform.compute_matrix() * camera.projection_matrix().inverse();
+    // World units per pixel
+    let camera_scale = camera_transform.compute_transform().scale.x;
+
+    let enemies = enemy_query.iter().map(|transform| (transform, COLOR_ENEMY));
+    // Debris isn't worth going after
+    let modules = object_query
+        .iter()
+        .filter(|(_, module)| {
+            module_definitions
+                .get(&module.id)
+                .map_or(false, |definition| {
+                    !matches!(definition.kind, ModuleKind::Debris)
+                })
+        })
+        .map(|(transform, _)| (transform, COLOR_PLAYER));
+
+    let center = viewport_size / 2.;
+    let bounds = center - Vec2::splat(INDICATOR_MARGIN);
+    let indicators = modules.chain(enemies).filter_map(|(transform, color)| {
+        let position = transform.translation;
+        let screen_position = camera.world_to_viewport(camera_transform, position)?;
+        if screen_position.cmpge(Vec2::ZERO).all() && screen_position.cmple(viewport_size).all() {
+            return None;
+        }
+
+        // Clamp to the edge of the screen, on the line from its center
+        let direction = screen_position - center;
+        let t = (bounds.x / direction.x.abs()).min(bounds.y / direction.y.abs());
+        let edge = center + direction * t;
+        let ndc = edge / viewport_size * 2. - Vec2::ONE;
+        let edge_position = viewport_to_world.project_point3(ndc.extend(-1.));
+
+        // Closer things get bigger arrows
+        let closeness =
+            1. - (position.truncate().distance(player_position) / INDICATOR_FAR_DISTANCE).min(1.);
+        let size = INDICATOR_MIN_SIZE + (INDICATOR_MAX_SIZE - INDICATOR_MIN_SIZE) * closeness;
+
+        let transform = Transform {
+            translation: edge_position.truncate().extend(INDICATOR_Z),
+            rotation: Quat::from_rotation_z(direction.y.atan2(direction.x)),
+            scale: Vec3::new(size * camera_scale, size * camera_scale, 1.),
+        };
+        Some((transform, color))
+    });
+
+    // Reuse the arrows of the last frame
+    let mut pool = indicator_query.iter_mut();
+    for (transform, color) in indicators {
+        match pool.next() {
+            Some((mut indicator_transform, mut sprite, mut visibility)) => {
+                *indicator_transform = transform;
+                sprite.color = color;
+                visibility.is_visible = true;
+            }
+            None => {
+                commands
+                    .spawn_bundle(SpriteBundle {
+                        transform,
+                        texture: sprite_handles.arrow.clone(),
+                        sprite: Sprite {
+                            color,
+                            custom_size: Some(Vec2::ONE),
+                            ..default()
+                        },
+                        ..default()
+                    })
+                    .insert(OffscreenIndicator)
+                    .insert(OnIngameScreen);
+            }
+        }
+    }
+
+    for (_, _, mut visibility) in pool {
+        visibility.is_visible = false;
+    }
+}
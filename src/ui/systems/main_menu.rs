@@ -1,22 +1,30 @@
 use crate::{
     asset::FontHandles,
     audio::AudioSettings,
+    camera::CameraSettings,
     colors::COLOR_TRANSPARENT,
+    damage_number::DamageNumberSettings,
+    replay::{Replay, ReplayMode, REPLAY_PATH},
+    save::{RunToRestore, SavedRun, SAVE_PATH},
     schedule::{GameState, ScheduleQueue},
     ui::helper::default_node_bundle_style,
     ui::{
-        components::{MainMenuButtonAction, OnMainMenuScreen, UiVolume},
+        components::{MainMenuButtonAction, OnMainMenuScreen, UiDamageNumbers, UiShake, UiVolume},
         helper::{
             accent_large_button_text_style, default_button_bundle, default_small_button_text_style,
+            on_off_label,
         },
     },
 };
 use bevy::{app::AppExit, prelude::*};
+use std::path::Path;
 
 pub fn spawn_main_menu_ui_system(
     mut commands: Commands,
     font_handles: Res<FontHandles>,
     audio_settings: Res<AudioSettings>,
+    camera_settings: Res<CameraSettings>,
+    damage_number_settings: Res<DamageNumberSettings>,
 ) {
     commands
         .spawn_bundle(NodeBundle {
@@ -47,6 +55,23 @@ pub fn spawn_main_menu_ui_system(
                 .with_text_alignment(TextAlignment::CENTER),
             );
 
+            // Continue button, only if there is a saved run that can be loaded. One from an older
+            // version never will be, so it's deleted
+            if Path::new(SAVE_PATH).exists() && SavedRun::load(SAVE_PATH).is_none() {
+                SavedRun::delete(SAVE_PATH);
+            }
+            if Path::new(SAVE_PATH).exists() {
+                parent
+                    .spawn_bundle(default_button_bundle())
+                    .insert(MainMenuButtonAction::Continue)
+                    .with_children(|parent| {
+                        parent.spawn_bundle(TextBundle::from_section(
+                            "Continue",
+                            default_small_button_text_style(font_handles.default.clone()),
+                        ));
+                    });
+            }
+
             // Play button
             parent
                 .spawn_bundle(default_button_bundle())
@@ -58,6 +83,19 @@ pub fn spawn_main_menu_ui_system(
                     ));
                 });
 
+            // Replay button, only if there is a recorded run that can be played back
+            if Path::new(REPLAY_PATH).exists() && Replay::load(REPLAY_PATH).is_some() {
+                parent
+                    .spawn_bundle(default_button_bundle())
+                    .insert(MainMenuButtonAction::Replay)
+                    .with_children(|parent| {
+                        parent.spawn_bundle(TextBundle::from_section(
+                            "Replay",
+                            default_small_button_text_style(font_handles.default.clone()),
+                        ));
+                    });
+            }
+
             // Volume button
             parent
                 .spawn_bundle(default_button_bundle())
@@ -77,6 +115,44 @@ pub fn spawn_main_menu_ui_system(
                         .insert(UiVolume);
                 });
 
+            // Shake button
+            parent
+                .spawn_bundle(default_button_bundle())
+                .insert(MainMenuButtonAction::Shake)
+                .with_children(|parent| {
+                    parent
+                        .spawn_bundle(TextBundle::from_sections([
+                            TextSection::new(
+                                "Shake: ",
+                                default_small_button_text_style(font_handles.default.clone()),
+                            ),
+                            TextSection::new(
+                                on_off_label(camera_settings.shake()),
+                                default_small_button_text_style(font_handles.default.clone()),
+                            ),
+                        ]))
+                        .insert(UiShake);
+                });
+
+            // Damage numbers button
+            parent
+                .spawn_bundle(default_button_bundle())
+                .insert(MainMenuButtonAction::DamageNumbers)
+                .with_children(|parent| {
+                    parent
+                        .spawn_bundle(TextBundle::from_sections([
+                            TextSection::new(
+                                "Damage: ",
+                                default_small_button_text_style(font_handles.default.clone()),
+                            ),
+                            TextSection::new(
+                                on_off_label(damage_number_settings.enabled()),
+                                default_small_button_text_style(font_handles.default.clone()),
+                            ),
+                        ]))
+                        .insert(UiDamageNumbers);
+                });
+
             // Quit button
             parent
                 .spawn_bundle(default_button_bundle())
@@ -90,22 +166,51 @@ pub fn spawn_main_menu_ui_system(
         });
 }
 
-#[allow(clippy::type_complexity)]
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 pub fn main_menu_button_interaction_system(
-    query: Query<(&Interaction, &MainMenuButtonAction), (Changed<Interaction>, With<Button>)>,
+    mut commands: Commands,
+    query: Query<
+        (Entity, &Interaction, &MainMenuButtonAction),
+        (Changed<Interaction>, With<Button>),
+    >,
     mut app_exit_events: EventWriter<AppExit>,
     mut game_state: ResMut<State<GameState>>,
     mut schedule_queue: ResMut<ScheduleQueue>,
     mut audio_settings: ResMut<AudioSettings>,
+    mut camera_settings: ResMut<CameraSettings>,
+    mut damage_number_settings: ResMut<DamageNumberSettings>,
+    mut replay_mode: ResMut<ReplayMode>,
+    mut run_to_restore: ResMut<RunToRestore>,
 ) {
-    for (interaction, action) in query.iter() {
+    for (entity, interaction, action) in query.iter() {
         if *interaction == Interaction::Clicked {
             match action {
+                MainMenuButtonAction::Continue => match SavedRun::load(SAVE_PATH) {
+                    Some(run) => {
+                        run_to_restore.0 = Some(run);
+                        game_state.set(GameState::AfterMainMenu).unwrap();
+                        schedule_queue.0.push_back(GameState::BeforeInGame);
+                    }
+                    // The save broke since the menu was opened, there is nothing to continue
+                    None => {
+                        SavedRun::delete(SAVE_PATH);
+                        commands.entity(entity).despawn_recursive();
+                    }
+                },
                 MainMenuButtonAction::Play => {
                     game_state.set(GameState::AfterMainMenu).unwrap();
                     schedule_queue.0.push_back(GameState::BeforeInGame);
                 }
+                MainMenuButtonAction::Replay => {
+                    if let Some(replay) = Replay::load(REPLAY_PATH) {
+                        *replay_mode = ReplayMode::playback(replay);
+                        game_state.set(GameState::AfterMainMenu).unwrap();
+                        schedule_queue.0.push_back(GameState::BeforeInGame);
+                    }
+                }
                 MainMenuButtonAction::Volume => audio_settings.toggle(),
+                MainMenuButtonAction::Shake => camera_settings.toggle_shake(),
+                MainMenuButtonAction::DamageNumbers => damage_number_settings.toggle(),
                 MainMenuButtonAction::Quit => app_exit_events.send(AppExit),
             }
         }
@@ -120,3 +225,21 @@ pub fn update_ui_volume_system(
     let section = &mut ui_volume.sections[1];
     section.value = format!("{}%", audio_settings.volume());
 }
+
+pub fn update_ui_shake_system(
+    camera_settings: Res<CameraSettings>,
+    mut ui_query: Query<&mut Text, With<UiShake>>,
+) {
+    let mut ui_shake = ui_query.single_mut();
+    let section = &mut ui_shake.sections[1];
+    section.value = on_off_label(camera_settings.shake());
+}
+
+pub fn update_ui_damage_numbers_system(
+    damage_number_settings: Res<DamageNumberSettings>,
+    mut ui_query: Query<&mut Text, With<UiDamageNumbers>>,
+) {
+    let mut ui_damage_numbers = ui_query.single_mut();
+    let section = &mut ui_damage_numbers.sections[1];
+    section.value = on_off_label(damage_number_settings.enabled());
+}
@@ -1,8 +1,14 @@
 mod end_screen;
 mod general;
+mod health_bars;
+mod indicators;
 mod ingame;
 mod main_menu;
 mod paused;
+mod radar;
 mod splash_screen;
 
-pub use self::{end_screen::*, general::*, ingame::*, main_menu::*, paused::*, splash_screen::*};
+pub use self::{
+    end_screen::*, general::*, health_bars::*, indicators::*, ingame::*, main_menu::*, paused::*,
+    radar::*, splash_screen::*,
+};
@@ -1,12 +1,18 @@
 use crate::{
     asset::FontHandles,
     audio::AudioSettings,
+    camera::CameraSettings,
     colors::COLOR_TRANSPARENT,
+    damage_number::DamageNumberSettings,
+    replay::ReplayMode,
+    save::{RunSnapshot, SAVE_PATH},
     schedule::{GameState, ScheduleQueue},
     ui::helper::default_small_button_text_style,
     ui::{
-        components::{OnPausedScreen, PausedScreenButtonAction, UiVolume},
-        helper::{default_button_bundle, default_node_bundle_style},
+        components::{
+            OnPausedScreen, PausedScreenButtonAction, UiDamageNumbers, UiShake, UiVolume,
+        },
+        helper::{default_button_bundle, default_node_bundle_style, on_off_label},
     },
 };
 use bevy::{app::AppExit, prelude::*};
@@ -15,6 +21,8 @@ pub fn spawn_paused_ui_system(
     mut commands: Commands,
     font_handles: Res<FontHandles>,
     audio_settings: Res<AudioSettings>,
+    camera_settings: Res<CameraSettings>,
+    damage_number_settings: Res<DamageNumberSettings>,
 ) {
     commands
         .spawn_bundle(NodeBundle {
@@ -68,6 +76,44 @@ pub fn spawn_paused_ui_system(
                         .insert(UiVolume);
                 });
 
+            // Shake button
+            parent
+                .spawn_bundle(default_button_bundle())
+                .insert(PausedScreenButtonAction::Shake)
+                .with_children(|parent| {
+                    parent
+                        .spawn_bundle(TextBundle::from_sections([
+                            TextSection::new(
+                                "Shake: ",
+                                default_small_button_text_style(font_handles.default.clone()),
+                            ),
+                            TextSection::new(
+                                on_off_label(camera_settings.shake()),
+                                default_small_button_text_style(font_handles.default.clone()),
+                            ),
+                        ]))
+                        .insert(UiShake);
+                });
+
+            // Damage numbers button
+            parent
+                .spawn_bundle(default_button_bundle())
+                .insert(PausedScreenButtonAction::DamageNumbers)
+                .with_children(|parent| {
+                    parent
+                        .spawn_bundle(TextBundle::from_sections([
+                            TextSection::new(
+                                "Damage: ",
+                                default_small_button_text_style(font_handles.default.clone()),
+                            ),
+                            TextSection::new(
+                                on_off_label(damage_number_settings.enabled()),
+                                default_small_button_text_style(font_handles.default.clone()),
+                            ),
+                        ]))
+                        .insert(UiDamageNumbers);
+                });
+
             // Quit button
             parent
                 .spawn_bundle(default_button_bundle())
@@ -81,24 +127,42 @@ pub fn spawn_paused_ui_system(
         });
 }
 
-#[allow(clippy::type_complexity)]
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 pub fn paused_button_interaction_system(
     query: Query<(&Interaction, &PausedScreenButtonAction), (Changed<Interaction>, With<Button>)>,
     mut app_exit_events: EventWriter<AppExit>,
     mut game_state: ResMut<State<GameState>>,
     mut schedule_queue: ResMut<ScheduleQueue>,
     mut audio_settings: ResMut<AudioSettings>,
+    mut camera_settings: ResMut<CameraSettings>,
+    mut damage_number_settings: ResMut<DamageNumberSettings>,
+    run_snapshot: RunSnapshot,
+    replay_mode: Res<ReplayMode>,
 ) {
+    // A replay that is played back isn't the player's own run, saving it would replace that
+    let is_live_run = !matches!(*replay_mode, ReplayMode::Playback { .. });
+
     for (interaction, action) in query.iter() {
         if *interaction == Interaction::Clicked {
             match action {
                 PausedScreenButtonAction::Continue => game_state.pop().unwrap(),
                 PausedScreenButtonAction::Volume => audio_settings.toggle(),
+                PausedScreenButtonAction::Shake => camera_settings.toggle_shake(),
+                PausedScreenButtonAction::DamageNumbers => damage_number_settings.toggle(),
                 PausedScreenButtonAction::MainMenu => {
+                    // Right away, as leaving the game breaks the ship apart in this same frame
+                    if is_live_run {
+                        run_snapshot.save(SAVE_PATH);
+                    }
                     game_state.set(GameState::AfterInGame).unwrap();
                     schedule_queue.0.push_back(GameState::BeforeMainMenu);
                 }
-                PausedScreenButtonAction::Quit => app_exit_events.send(AppExit),
+                PausedScreenButtonAction::Quit => {
+                    if is_live_run {
+                        run_snapshot.save(SAVE_PATH);
+                    }
+                    app_exit_events.send(AppExit);
+                }
             }
         }
     }
@@ -0,0 +1,151 @@
+use crate::{
+    colors::{COLOR_PLAYER, COLOR_TRANSPARENT},
+    components::Projectile,
+    enemy::EnemyRoot,
+    faction::Faction,
+    module::{Module, ModuleDefinitions, ModuleKind},
+    object::Object,
+    player::PlayerRoot,
+    ui::{
+        components::{OnIngameScreen, UiRadar, UiRadarBlip},
+        constants::{
+            COLOR_RADAR_BACKGROUND, COLOR_RADAR_CANNON, COLOR_RADAR_DEBRIS, COLOR_RADAR_SHIELD,
+            COLOR_RADAR_ZAPPER, RADAR_BLIP_SIZE, RADAR_SIZE,
+        },
+        resources::RadarSettings,
+    },
+};
+use bevy::prelude::*;
+
+fn blip_bundle(offset: Vec2, color: Color) -> NodeBundle {
+    // UI positions go from the top left corner downwards
+    let position = Vec2::new(RADAR_SIZE / 2. + offset.x, RADAR_SIZE / 2. - offset.y)
+        - Vec2::splat(RADAR_BLIP_SIZE / 2.);
+
+    NodeBundle {
+        style: Style {
+            position_type: PositionType::Absolute,
+            position: UiRect::new(
+                Val::Px(position.x),
+                Val::Undefined,
+                Val::Px(position.y),
+                Val::Undefined,
+            ),
+            size: Size::new(Val::Px(RADAR_BLIP_SIZE), Val::Px(RADAR_BLIP_SIZE)),
+            ..default()
+        },
+        color: color.into(),
+        ..default()
+    }
+}
+
+// Top right, across from the score
+pub fn spawn_radar_ui_system(mut commands: Commands) {
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: UiRect::new(Val::Undefined, Val::Px(25.0), Val::Px(25.0), Val::Undefined),
+                size: Size::new(Val::Px(RADAR_SIZE), Val::Px(RADAR_SIZE)),
+                ..default()
+            },
+            color: COLOR_RADAR_BACKGROUND.into(),
+            ..default()
+        })
+        .insert(OnIngameScreen)
+        .insert(UiRadar)
+        .with_children(|parent| {
+            // The player is always in the center
+            parent.spawn_bundle(blip_bundle(Vec2::ZERO, COLOR_PLAYER));
+        });
+}
+
+// Modules without a definition show up like debris
+fn module_color(kind: Option<&ModuleKind>) -> Color {
+    match kind {
+        None | Some(ModuleKind::Debris) => COLOR_RADAR_DEBRIS,
+        Some(ModuleKind::Zapper(_)) => COLOR_RADAR_ZAPPER,
+        Some(ModuleKind::Cannon(_)) => COLOR_RADAR_CANNON,
+        Some(ModuleKind::Shield(_)) => COLOR_RADAR_SHIELD,
+    }
+}
+
+pub fn read_radar_zoom_input_system(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut radar_settings: ResMut<RadarSettings>,
+) {
+    if keyboard_input.just_pressed(KeyCode::R) {
+        radar_settings.cycle_zoom();
+    }
+}
+
+// Plots everything around the player, reusing the blips of the last frame
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn update_ui_radar_system(
+    mut commands: Commands,
+    radar_settings: Res<RadarSettings>,
+    module_definitions: Res<ModuleDefinitions>,
+    player_query: Query<&Transform, With<PlayerRoot>>,
+    enemy_query: Query<(&Transform, &Faction), With<EnemyRoot>>,
+    object_query: Query<(&Transform, &Module), (With<Object>, Without<Parent>)>,
+    projectile_query: Query<(&Transform, &Faction), With<Projectile>>,
+    radar_query: Query<Entity, With<UiRadar>>,
+    mut blip_query: Query<(&mut Style, &mut UiColor), With<UiRadarBlip>>,
+) {
+    let player_position = player_query.single().translation.truncate();
+    let radar = radar_query.single();
+    let scale = RADAR_SIZE / 2. / radar_settings.visible_range();
+
+    let enemies = enemy_query
+        .iter()
+        .map(|(transform, faction)| (transform, faction.color()));
+    let objects = object_query.iter().map(|(transform, module)| {
+        (
+            transform,
+            module_color(
+                module_definitions
+                    .get(&module.id)
+                    .map(|definition| &definition.kind),
+            ),
+        )
+    });
+    let projectiles = projectile_query
+        .iter()
+        .filter(|(_, faction)| Faction::Player.is_hostile_to(**faction))
+        .map(|(transform, faction)| (transform, faction.color()));
+
+    let blips = objects
+        .chain(projectiles)
+        // Last, so ships are drawn on top
+        .chain(enemies)
+        .filter_map(|(transform, color)| {
+            let offset = (transform.translation.truncate() - player_position) * scale;
+            let edge = (RADAR_SIZE - RADAR_BLIP_SIZE) / 2.;
+            if offset.x.abs() > edge || offset.y.abs() > edge {
+                return None;
+            }
+            Some((offset, color))
+        });
+
+    let mut pool = blip_query.iter_mut();
+    for (offset, color) in blips {
+        let bundle = blip_bundle(offset, color);
+        match pool.next() {
+            Some((mut style, mut ui_color)) => {
+                *style = bundle.style;
+                *ui_color = bundle.color;
+            }
+            // Grow the pool when there is more to show than ever before
+            None => {
+                commands.entity(radar).with_children(|parent| {
+                    parent.spawn_bundle(bundle).insert(UiRadarBlip);
+                });
+            }
+        }
+    }
+
+    // Hide the blips that aren't needed this frame
+    for (_, mut ui_color) in pool {
+        *ui_color = COLOR_TRANSPARENT.into();
+    }
+}
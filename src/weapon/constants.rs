@@ -0,0 +1,6 @@
+// Velocity a hit pushes onto a ship of size 1, bigger ships are pushed less
+pub const ZAPPER_KNOCKBACK: f32 = 10.;
+pub const CANNON_KNOCKBACK: f32 = 40.;
+
+// Seconds a zap stays visible while fading out
+pub const ZAPPER_BEAM_LIFETIME: f32 = 0.15;
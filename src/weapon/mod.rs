@@ -0,0 +1,5 @@
+mod constants;
+mod plugin;
+mod systems;
+
+pub use self::plugin::{WeaponPlugin, WeaponSystem};
@@ -0,0 +1,31 @@
+use super::systems::{projectile_hit_system, shoot_cannon_system, shoot_zapper_system};
+use crate::{
+    physics::{on_physics_update, PhysicsStage, PhysicsSystem},
+    player::PlayerSystem,
+    schedule::GameState,
+    spatial::SpatialSystem,
+};
+use bevy::prelude::*;
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash, SystemLabel)]
+pub struct WeaponSystem;
+
+// The weapons of every faction
+pub struct WeaponPlugin;
+
+impl Plugin for WeaponPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set_to_stage(
+            PhysicsStage,
+            on_physics_update(GameState::InGame)
+                .label(WeaponSystem)
+                .after(PhysicsSystem::Propagate)
+                .after(SpatialSystem)
+                // So the hits are applied in the same step
+                .before(PlayerSystem)
+                .with_system(shoot_zapper_system)
+                .with_system(shoot_cannon_system)
+                .with_system(projectile_hit_system),
+        );
+    }
+}
@@ -0,0 +1,298 @@
+use super::constants::{CANNON_KNOCKBACK, ZAPPER_BEAM_LIFETIME, ZAPPER_KNOCKBACK};
+use crate::{
+    audio::{AudioEvent, AudioType},
+    collision::{colliders_overlap, segment_entry},
+    components::{
+        Bullet, Cannon, Collider, DamageType, Health, Projectile, ShieldForcefield, Velocity,
+        Zapper,
+    },
+    effect::spawn_beam,
+    events::{ForcefieldHit, Hit, WeaponKind},
+    faction::Faction,
+    object::{BULLET_COLLIDER, PROJECTILE_SPEED},
+    physics::PHYSICS_TIMESTEP,
+    spatial::SpatialIndex,
+};
+use bevy::prelude::*;
+
+type TargetQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static Faction,
+        &'static Collider,
+        &'static GlobalTransform,
+    ),
+    (With<Health>, Without<ShieldForcefield>),
+>;
+
+// Forcefields fight for the ship their shield, the parent, belongs to
+type ForcefieldQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static mut ShieldForcefield,
+        &'static Collider,
+        &'static GlobalTransform,
+        &'static Parent,
+    ),
+>;
+
+// The closest forcefield hostile to `faction` that is up and that the path from `from` to
+// `to` runs into, with the point where it does
+fn find_blocking_forcefield(
+    spatial_index: &SpatialIndex,
+    forcefield_query: &ForcefieldQuery,
+    shield_query: &Query<&Faction>,
+    faction: Faction,
+    from: Vec2,
+    to: Vec2,
+) -> Option<(Entity, Vec2)> {
+    spatial_index
+        .query_colliders(from.lerp(to, 0.5), from.distance(to) / 2.)
+        .filter_map(|(entity, _)| {
+            let (stats, collider, transform, shield) = forcefield_query.get(entity).ok()?;
+            let is_hostile = shield_query
+                .get(shield.get())
+                .map_or(false, |shield_faction| {
+                    faction.is_hostile_to(*shield_faction)
+                });
+            if !stats.is_up() || !is_hostile {
+                return None;
+            }
+
+            segment_entry(collider, &transform.compute_transform(), from, to)
+                .map(|entry| (entity, entry))
+        })
+        .min_by(|(_, a), (_, b)| {
+            a.distance_squared(from)
+                .total_cmp(&b.distance_squared(from))
+        })
+}
+
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn shoot_zapper_system(
+    mut commands: Commands,
+    spatial_index: Res<SpatialIndex>,
+    mut event_hit: EventWriter<Hit>,
+    mut event_forcefield_hit: EventWriter<ForcefieldHit>,
+    mut event_audio: EventWriter<AudioEvent>,
+    mut zapper_query: Query<(Entity, &GlobalTransform, &mut Zapper, &Faction)>,
+    target_query: Query<&Faction, (With<Health>, Without<ShieldForcefield>)>,
+    mut forcefield_query: ForcefieldQuery,
+    shield_query: Query<&Faction>,
+) {
+    for (zapper_entity, zapper_transform, mut zapper_stats, faction) in zapper_query.iter_mut() {
+        if zapper_stats.cooldown_timer > 0. {
+            zapper_stats.cooldown_timer -= PHYSICS_TIMESTEP;
+            continue;
+        }
+
+        // Zap the closest hostile node in range
+        let zapper_position = zapper_transform.translation().truncate();
+        let target = spatial_index.nearest(zapper_position, zapper_stats.range, |entity| {
+            target_query.get(entity).map_or(false, |target_faction| {
+                faction.is_hostile_to(*target_faction)
+            })
+        });
+
+        if let Some((target_entity, target_position)) = target {
+            zapper_stats.cooldown_timer = zapper_stats.fire_rate;
+            event_audio.send(AudioEvent(AudioType::Laser));
+            event_audio.send(AudioEvent(AudioType::Hit));
+
+            // A forcefield in the way takes the zap instead
+            let blocking_forcefield = find_blocking_forcefield(
+                &spatial_index,
+                &forcefield_query,
+                &shield_query,
+                *faction,
+                zapper_position,
+                target_position,
+            );
+            let impact_position = match blocking_forcefield {
+                Some((forcefield_entity, entry)) => {
+                    if let Ok((mut forcefield_stats, ..)) =
+                        forcefield_query.get_mut(forcefield_entity)
+                    {
+                        event_forcefield_hit.send(ForcefieldHit {
+                            damage: forcefield_stats
+                                .absorb(zapper_stats.damage, DamageType::Energy),
+                            position: entry,
+                        });
+                    }
+                    entry
+                }
+                None => {
+                    event_hit.send(Hit {
+                        target: target_entity,
+                        damage: zapper_stats.damage,
+                        damage_type: DamageType::Energy,
+                        source: Some(zapper_entity),
+                        position: target_position,
+                        weapon: WeaponKind::Zapper,
+                        impulse: (target_position - zapper_position).normalize_or_zero()
+                            * ZAPPER_KNOCKBACK,
+                    });
+                    target_position
+                }
+            };
+
+            spawn_beam(
+                &mut commands,
+                zapper_position,
+                impact_position,
+                faction.color(),
+                ZAPPER_BEAM_LIFETIME,
+            );
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn shoot_cannon_system(
+    mut commands: Commands,
+    spatial_index: Res<SpatialIndex>,
+    mut event_audio: EventWriter<AudioEvent>,
+    mut cannon_query: Query<(Entity, &GlobalTransform, &mut Cannon, &Faction)>,
+    target_query: Query<&Faction, (With<Health>, Without<ShieldForcefield>)>,
+) {
+    for (cannon_entity, cannon_transform, mut cannon_stats, faction) in cannon_query.iter_mut() {
+        if cannon_stats.cooldown_timer > 0. {
+            cannon_stats.cooldown_timer -= PHYSICS_TIMESTEP;
+            continue;
+        }
+
+        // Shoot at the closest hostile node in range, the bullet does the damage
+        let cannon_position = cannon_transform.translation();
+        let target =
+            spatial_index.nearest(cannon_position.truncate(), cannon_stats.range, |entity| {
+                target_query.get(entity).map_or(false, |target_faction| {
+                    faction.is_hostile_to(*target_faction)
+                })
+            });
+
+        if let Some((_, target_position)) = target {
+            cannon_stats.cooldown_timer = cannon_stats.fire_rate;
+            event_audio.send(AudioEvent(AudioType::Explosion));
+
+            let direction = (target_position - cannon_position.truncate()).normalize_or_zero();
+
+            // Shoot a bullet
+            commands
+                .spawn()
+                .insert_bundle(SpriteBundle {
+                    transform: Transform {
+                        translation: cannon_position,
+                        scale: Vec3::new(2., 2., 0.),
+                        ..default()
+                    },
+                    sprite: Sprite {
+                        color: faction.color(),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .insert(Projectile {})
+                .insert(BULLET_COLLIDER)
+                .insert(*faction)
+                .insert(Bullet {
+                    damage: cannon_stats.damage,
+                    damage_type: cannon_stats.damage_type,
+                    source: Some(cannon_entity),
+                })
+                .insert(Velocity {
+                    x: direction.x * PROJECTILE_SPEED,
+                    y: direction.y * PROJECTILE_SPEED,
+                    rotation: 0.,
+                });
+        }
+    }
+}
+
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn projectile_hit_system(
+    mut commands: Commands,
+    mut event_hit: EventWriter<Hit>,
+    mut event_forcefield_hit: EventWriter<ForcefieldHit>,
+    mut event_audio: EventWriter<AudioEvent>,
+    spatial_index: Res<SpatialIndex>,
+    bullet_query: Query<(
+        Entity,
+        &GlobalTransform,
+        &Collider,
+        &Bullet,
+        &Velocity,
+        &Faction,
+    )>,
+    target_query: TargetQuery,
+    mut forcefield_query: ForcefieldQuery,
+    shield_query: Query<&Faction>,
+) {
+    'bullets: for (
+        bullet_entity,
+        bullet_transform,
+        bullet_collider,
+        bullet_stats,
+        bullet_velocity,
+        faction,
+    ) in bullet_query.iter()
+    {
+        // In world space like the targets and the spatial index
+        let bullet_transform = bullet_transform.compute_transform();
+        let bullet_position = bullet_transform.translation.truncate();
+        let bullet_radius = bullet_collider.bounding_radius(bullet_transform.scale);
+        let velocity = Vec2::new(bullet_velocity.x, bullet_velocity.y);
+
+        // Forcefields take the bullets that flew into them since the last step
+        let previous_position = bullet_position - velocity * PHYSICS_TIMESTEP;
+        if let Some((forcefield_entity, entry)) = find_blocking_forcefield(
+            &spatial_index,
+            &forcefield_query,
+            &shield_query,
+            *faction,
+            previous_position,
+            bullet_position,
+        ) {
+            if let Ok((mut forcefield_stats, ..)) = forcefield_query.get_mut(forcefield_entity) {
+                event_forcefield_hit.send(ForcefieldHit {
+                    damage: forcefield_stats.absorb(bullet_stats.damage, bullet_stats.damage_type),
+                    position: entry,
+                });
+            }
+            commands.entity(bullet_entity).despawn();
+            continue;
+        }
+
+        // If no forcefield, check for a hostile node
+        // On collision, do hit event and remove bullet
+        for (target_entity, _) in spatial_index.query_colliders(bullet_position, bullet_radius) {
+            if let Ok((target_faction, target_collider, target_transform)) =
+                target_query.get(target_entity)
+            {
+                if !faction.is_hostile_to(*target_faction)
+                    || !colliders_overlap(
+                        bullet_collider,
+                        &bullet_transform,
+                        target_collider,
+                        &target_transform.compute_transform(),
+                    )
+                {
+                    continue;
+                }
+                event_hit.send(Hit {
+                    target: target_entity,
+                    damage: bullet_stats.damage,
+                    damage_type: bullet_stats.damage_type,
+                    source: bullet_stats.source,
+                    position: bullet_position,
+                    weapon: WeaponKind::Cannon,
+                    impulse: velocity.normalize_or_zero() * CANNON_KNOCKBACK,
+                });
+                event_audio.send(AudioEvent(AudioType::Hit));
+                commands.entity(bullet_entity).despawn();
+                continue 'bullets;
+            }
+        }
+    }
+}
@@ -1,3 +1,7 @@
+mod constants;
 mod plugin;
 
-pub use self::plugin::{WindowPlugin, WindowSystem};
+pub use self::{
+    constants::{WINDOW_HEIGHT, WINDOW_WIDTH},
+    plugin::{WindowPlugin, WindowSystem},
+};
@@ -1,3 +1,4 @@
+use super::{WINDOW_HEIGHT, WINDOW_WIDTH};
 use bevy::{
     prelude::*,
     window::{PresentMode, WindowMode, WindowResizeConstraints},
@@ -12,8 +13,8 @@ impl Plugin for WindowPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(WindowDescriptor {
             title: String::from("Escape Pod"),
-            width: 1280.0,
-            height: 720.0,
+            width: WINDOW_WIDTH,
+            height: WINDOW_HEIGHT,
             position: WindowPosition::Automatic,
             resize_constraints: WindowResizeConstraints::default(),
             scale_factor_override: None,